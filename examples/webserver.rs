@@ -2,58 +2,51 @@ use std::ffi::OsStr;
 
 use axum::{
     Router,
-    extract::Path,
+    extract::{Path, State},
     http::{StatusCode, header},
     response::IntoResponse,
     routing::get,
 };
 use identicon_rs::Identicon;
+use identicon_rs::renderer::{ExportFormat, Renderer};
 
 #[tokio::main]
 async fn main() {
-    let app = Router::new().route("/{input}", get(root));
+    // The renderer is built once and shared between all requests
+    let renderer = Identicon::default().renderer();
+    let app = Router::new()
+        .route("/{input}", get(root))
+        .with_state(renderer);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
 
 // basic handler that responds with a static string
-async fn root(Path(input): Path<String>) -> impl IntoResponse {
+async fn root(State(renderer): State<Renderer>, Path(input): Path<String>) -> impl IntoResponse {
     let file_path = std::path::Path::new(&input);
     match file_path.file_stem() {
         None => StatusCode::BAD_REQUEST.into_response(),
         Some(input) => match input.to_str() {
             None => StatusCode::BAD_REQUEST.into_response(),
-            Some(name) => {
-                let identicon: Identicon = Identicon::new(name);
-                generate_image(file_path.extension(), &identicon).into_response()
-            }
+            Some(name) => generate_image(file_path.extension(), &renderer, name).into_response(),
         },
     }
 }
 
-fn generate_image(extention: Option<&OsStr>, identicon: &Identicon) -> impl IntoResponse {
-    match extention {
-        Some(os_str) => match os_str.to_str() {
-            Some("jpeg") | Some("jpg") => (
-                StatusCode::OK,
-                [(header::CONTENT_TYPE, "image/jpeg")],
-                identicon.export_jpeg_data().unwrap(),
-            )
-                .into_response(),
-            Some("png") => (
-                StatusCode::OK,
-                [(header::CONTENT_TYPE, "image/png")],
-                identicon.export_png_data().unwrap(),
-            )
-                .into_response(),
-            _ => StatusCode::BAD_REQUEST.into_response(),
+fn generate_image(extention: Option<&OsStr>, renderer: &Renderer, name: &str) -> impl IntoResponse {
+    let format = match extention.map(OsStr::to_str) {
+        None => ExportFormat::Png,
+        Some(extension) => match extension.and_then(ExportFormat::from_extension) {
+            Some(format) => format,
+            None => return StatusCode::BAD_REQUEST.into_response(),
         },
-        None => (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, "image/png")],
-            identicon.export_png_data().unwrap(),
-        )
-            .into_response(),
-    }
+    };
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, format.mime_type())],
+        renderer.encode(name, format).unwrap(),
+    )
+        .into_response()
 }
@@ -0,0 +1,678 @@
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{Parser, ValueEnum};
+use identicon_rs::Identicon;
+use identicon_rs::cell::{CellGap, CellStyle};
+use identicon_rs::color::{ContrastMetric, RGB};
+use identicon_rs::error::IdenticonError;
+use identicon_rs::mask::Mask;
+use identicon_rs::pattern::{Bits, EvenBytes, MultiTone, Nibbles};
+use identicon_rs::renderer::{ExportFormat, Renderer};
+use identicon_rs::style::Style;
+use identicon_rs::symmetry::Symmetry;
+use identicon_rs::theme::error::ThemeError;
+use identicon_rs::theme::{self, DerivedBackground, HSLRange, MinimumContrast, Theme};
+
+/// Generate identicon images from input text.
+#[derive(Parser, Debug)]
+#[command(name = "identicon", version)]
+struct Args {
+    /// The input text to generate an identicon for.
+    ///
+    /// Required unless `--batch` is used.
+    input: Option<String>,
+
+    /// Read one input per line from stdin and write one file per input.
+    #[arg(long, conflicts_with = "input")]
+    batch: bool,
+
+    /// The output file, or `-` for stdout.
+    ///
+    /// In batch mode this is the output directory.
+    /// Defaults to stdout, or the current directory in batch mode.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// The output format, such as png, jpeg, or svg.
+    ///
+    /// Defaults to the output file extension, falling back to png.
+    #[arg(short, long, value_parser = parse_format)]
+    format: Option<ExportFormat>,
+
+    /// The number of viewable blocks of the identicon, such as `5` or `8x4`.
+    #[arg(long, default_value = "5", value_parser = parse_dimensions)]
+    size: (u32, u32),
+
+    /// The height and width of the identicon portion of the image, such as `500` or `600x200`.
+    #[arg(long, default_value = "500", value_parser = parse_dimensions)]
+    scale: (u32, u32),
+
+    /// The border size.
+    #[arg(long, default_value_t = 50)]
+    border: u32,
+
+    /// The symmetry of the identicon grid.
+    #[arg(long, value_enum, default_value_t = SymmetryName::Vertical)]
+    symmetry: SymmetryName,
+
+    /// The overall style of the identicon.
+    #[arg(long, value_enum, default_value_t = StyleName::Grid)]
+    style: StyleName,
+
+    /// The pattern turning the hash into the grid.
+    #[arg(long, value_enum, default_value_t = PatternName::EvenBytes)]
+    pattern: PatternName,
+
+    /// The number of palette colors for multi-tone patterns and the geometric style, including the
+    /// main color.
+    ///
+    /// Requires an HSL or Oklch range theme.
+    #[arg(long, default_value_t = 1)]
+    colors: usize,
+
+    /// The built-in theme to use.
+    #[arg(long, value_enum, default_value_t = ThemeName::Default)]
+    theme: ThemeName,
+
+    /// Use a custom HSL range theme with this minimum hue (0-360).
+    #[arg(long)]
+    hue_min: Option<f32>,
+
+    /// Use a custom HSL range theme with this maximum hue (0-360).
+    ///
+    /// A maximum below the minimum wraps around 360, such as `--hue-min 330 --hue-max 30`.
+    #[arg(long)]
+    hue_max: Option<f32>,
+
+    /// Use a custom HSL range theme with this further hue band, such as `20-40`.
+    ///
+    /// Can be repeated, and the hash picks uniformly from all bands.
+    #[arg(long, value_parser = parse_hue_band)]
+    hue_band: Vec<(f32, f32)>,
+
+    /// Use a custom HSL range theme with this minimum saturation (0-100).
+    #[arg(long)]
+    saturation_min: Option<f32>,
+
+    /// Use a custom HSL range theme with this maximum saturation (0-100).
+    #[arg(long)]
+    saturation_max: Option<f32>,
+
+    /// Use a custom HSL range theme with this minimum lightness (0-100).
+    #[arg(long)]
+    lightness_min: Option<f32>,
+
+    /// Use a custom HSL range theme with this maximum lightness (0-100).
+    #[arg(long)]
+    lightness_max: Option<f32>,
+
+    /// The background color of HSL range themes, derived from the main color unless gray.
+    ///
+    /// Uses a custom HSL range theme unless gray.
+    #[arg(long, value_enum, default_value_t = BackgroundName::Gray)]
+    background: BackgroundName,
+
+    /// Adjust the lightness of the colors until they reach this minimum contrast against the
+    /// background, such as `3` for WCAG or `45` for APCA.
+    #[arg(long)]
+    min_contrast: Option<f32>,
+
+    /// The metric used by `--min-contrast`.
+    #[arg(long, value_enum, default_value_t = ContrastMetricName::Wcag2)]
+    contrast_metric: ContrastMetricName,
+
+    /// Render a fully transparent background.
+    #[arg(long)]
+    transparent: bool,
+
+    /// The shape drawn for every active cell.
+    #[arg(long, value_enum, default_value_t = CellStyleName::Square)]
+    cell_style: CellStyleName,
+
+    /// The gap between neighbouring cells, in pixels such as `10`, or as a fraction of the cell
+    /// size such as `0.1`.
+    #[arg(long, default_value = "0", value_parser = parse_cell_gap)]
+    cell_gap: CellGap,
+
+    /// The shape mask of the image, making the pixels outside of it transparent.
+    #[arg(long, value_enum, default_value_t = MaskName::None)]
+    mask: MaskName,
+
+    /// The corner radius of the rounded rectangle mask, in pixels.
+    #[arg(long, default_value_t = 100)]
+    mask_radius: u32,
+}
+
+fn parse_format(value: &str) -> Result<ExportFormat, String> {
+    ExportFormat::from_extension(value).ok_or_else(|| format!("unsupported format: {value}"))
+}
+
+/// Parses `MIN-MAX` hue bands.
+fn parse_hue_band(value: &str) -> Result<(f32, f32), String> {
+    let parse = |number: &str| {
+        number
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| format!("invalid hue band: {value}"))
+    };
+    let (min, max) = value
+        .split_once('-')
+        .ok_or_else(|| format!("invalid hue band: {value}"))?;
+    Ok((parse(min)?, parse(max)?))
+}
+
+/// Parses `N` as `N`x`N`, or `WIDTHxHEIGHT`.
+fn parse_dimensions(value: &str) -> Result<(u32, u32), String> {
+    let parse = |number: &str| {
+        number
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("invalid dimensions: {value}"))
+    };
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => Ok((parse(width)?, parse(height)?)),
+        None => parse(value).map(|size| (size, size)),
+    }
+}
+
+/// Parses whole numbers as pixels, and decimal numbers as fractions of the cell size.
+fn parse_cell_gap(value: &str) -> Result<CellGap, String> {
+    let gap = if value.contains('.') {
+        value.parse().ok().map(CellGap::Fraction)
+    } else {
+        value.parse().ok().map(CellGap::Pixels)
+    };
+    gap.ok_or_else(|| format!("invalid cell gap: {value}"))
+}
+
+fn format_from_path(path: &Path) -> Option<ExportFormat> {
+    ExportFormat::from_extension(path.extension()?.to_str()?)
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SymmetryName {
+    /// No symmetry.
+    None,
+    /// Mirrored along the vertical axis, keeping the column right of the centre of even widths.
+    Vertical,
+    /// Mirrored along the vertical axis through the centre, also on even widths.
+    VerticalCentred,
+    /// Mirrored along the horizontal axis.
+    Horizontal,
+    /// Mirrored along both axes.
+    Both,
+    /// Mirrored along the diagonal.
+    Diagonal,
+    /// 2-fold rotational symmetry.
+    Rotational2,
+    /// 4-fold rotational symmetry.
+    Rotational4,
+}
+
+impl From<SymmetryName> for Symmetry {
+    fn from(value: SymmetryName) -> Self {
+        match value {
+            SymmetryName::None => Symmetry::None,
+            SymmetryName::Vertical => Symmetry::Vertical,
+            SymmetryName::VerticalCentred => Symmetry::VerticalCentred,
+            SymmetryName::Horizontal => Symmetry::Horizontal,
+            SymmetryName::Both => Symmetry::Both,
+            SymmetryName::Diagonal => Symmetry::Diagonal,
+            SymmetryName::Rotational2 => Symmetry::Rotational2,
+            SymmetryName::Rotational4 => Symmetry::Rotational4,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CellStyleName {
+    /// Squares covering the full cell.
+    Square,
+    /// Circles.
+    Circle,
+    /// Squares with rounded corners.
+    RoundedSquare,
+    /// Diamonds.
+    Diamond,
+    /// Triangles pointing in directions picked by the hash.
+    Triangle,
+}
+
+impl From<CellStyleName> for CellStyle {
+    fn from(value: CellStyleName) -> Self {
+        match value {
+            CellStyleName::Square => CellStyle::Square,
+            CellStyleName::Circle => CellStyle::Circle,
+            CellStyleName::RoundedSquare => CellStyle::RoundedSquare,
+            CellStyleName::Diamond => CellStyle::Diamond,
+            CellStyleName::Triangle => CellStyle::Triangle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum StyleName {
+    /// A grid of cells.
+    Grid,
+    /// Geometric shapes on 4x4 tiles.
+    Geometric,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum MaskName {
+    /// No mask.
+    None,
+    /// A circle.
+    Circle,
+    /// A rectangle with rounded corners, see `--mask-radius`.
+    Rounded,
+    /// A squircle.
+    Squircle,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum PatternName {
+    /// Each cell is active when its hash byte is even.
+    EvenBytes,
+    /// Each cell is active when its hash bit is set.
+    Bits,
+    /// Each cell is active when its hash nibble is even, filled from the middle column like GitHub.
+    Nibbles,
+    /// Each cell picks the background or a palette color, see `--colors`.
+    MultiTone,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ThemeName {
+    /// The default muted pastel HSL range theme.
+    Default,
+    /// The pastel selection theme.
+    PastelSelection,
+    /// The muted pastel Oklch range theme, with a perceptually even lightness.
+    Oklch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum BackgroundName {
+    /// The light gray background.
+    Gray,
+    /// A light complementary color, opposite the main color.
+    Complementary,
+    /// A light analogous color, next to the main color.
+    Analogous,
+    /// A light tint of the main color.
+    Tint,
+    /// A dark shade of the main color.
+    Shade,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ContrastMetricName {
+    /// The WCAG 2.x contrast ratio, from 1 to 21.
+    Wcag2,
+    /// The APCA lightness contrast, from 0 to about 108.
+    Apca,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let renderer = build_renderer(&args)?;
+    let output = args.output.filter(|output| output.as_os_str() != "-");
+
+    if args.batch {
+        let format = args.format.unwrap_or(ExportFormat::Png);
+        let directory = output.unwrap_or_else(|| PathBuf::from("."));
+        std::fs::create_dir_all(&directory)?;
+
+        let mut file_names = FileNames::default();
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let input = line.trim();
+            if input.is_empty() {
+                continue;
+            }
+
+            let Some(file_name) = file_names.file_name(input, format.extension()) else {
+                continue;
+            };
+            let data = renderer.encode(input, format)?;
+            std::fs::write(directory.join(file_name), data)?;
+        }
+        Ok(())
+    } else {
+        let input = args
+            .input
+            .ok_or("an input value is required unless --batch is used")?;
+        let format = args
+            .format
+            .or_else(|| output.as_deref().and_then(format_from_path))
+            .unwrap_or(ExportFormat::Png);
+        let data = renderer.encode(&input, format)?;
+
+        match output {
+            Some(path) => std::fs::write(path, data)?,
+            None => io::stdout().lock().write_all(&data)?,
+        }
+        Ok(())
+    }
+}
+
+fn build_renderer(args: &Args) -> Result<Renderer, IdenticonError> {
+    let mut identicon = Identicon::default();
+
+    // The scale must always be at least the size, so the order of the updates matters
+    let (size_width, size_height) = args.size;
+    let (scale_width, scale_height) = args.scale;
+    let (current_width, current_height) = identicon.grid_dimensions();
+    if scale_width >= current_width && scale_height >= current_height {
+        identicon
+            .set_scale_dimensions(scale_width, scale_height)?
+            .set_grid_dimensions(size_width, size_height)?;
+    } else {
+        identicon
+            .set_grid_dimensions(size_width, size_height)?
+            .set_scale_dimensions(scale_width, scale_height)?;
+    }
+
+    identicon
+        .set_border(args.border)
+        .set_style(match args.style {
+            StyleName::Grid => Style::Grid,
+            StyleName::Geometric => Style::Geometric,
+        })
+        .set_symmetry(args.symmetry.into())
+        .set_cell_style(args.cell_style.into())
+        .set_cell_gap(args.cell_gap)
+        .set_pattern(match args.pattern {
+            PatternName::EvenBytes => Arc::new(EvenBytes),
+            PatternName::Bits => Arc::new(Bits),
+            PatternName::Nibbles => Arc::new(Nibbles),
+            PatternName::MultiTone => Arc::new(MultiTone),
+        })
+        .set_mask(match args.mask {
+            MaskName::None => Mask::None,
+            MaskName::Circle => Mask::Circle,
+            MaskName::Rounded => Mask::RoundedRectangle {
+                radius: args.mask_radius,
+            },
+            MaskName::Squircle => Mask::Squircle,
+        })
+        .set_theme(build_theme(args)?);
+    if args.transparent {
+        identicon.set_background_alpha(Some(0));
+    }
+    Ok(identicon.renderer())
+}
+
+fn build_theme(args: &Args) -> Result<Arc<dyn Theme + Send + Sync>, IdenticonError> {
+    let theme = build_base_theme(args)?;
+    let Some(minimum) = args.min_contrast else {
+        return Ok(theme);
+    };
+    let metric = match args.contrast_metric {
+        ContrastMetricName::Wcag2 => ContrastMetric::Wcag2,
+        ContrastMetricName::Apca => ContrastMetric::Apca,
+    };
+    Ok(Arc::new(MinimumContrast::new(theme, metric, minimum)?))
+}
+
+fn build_base_theme(args: &Args) -> Result<Arc<dyn Theme + Send + Sync>, IdenticonError> {
+    let custom = [
+        args.hue_min,
+        args.hue_max,
+        args.saturation_min,
+        args.saturation_max,
+        args.lightness_min,
+        args.lightness_max,
+    ];
+    let custom_hsl = custom.iter().any(Option::is_some)
+        || !args.hue_band.is_empty()
+        || args.background != BackgroundName::Gray;
+    if custom_hsl && !matches!(args.theme, ThemeName::Default) {
+        let name = args
+            .theme
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        return Err(ThemeError::ThemeValidationError(format!(
+            "--theme {name} can not be combined with the HSL range options or --background"
+        ))
+        .into());
+    }
+    if !custom_hsl {
+        match args.theme {
+            ThemeName::Default if args.colors == 1 => return Ok(theme::default_theme()),
+            ThemeName::Default => {}
+            ThemeName::PastelSelection if args.colors == 1 => {
+                return Ok(theme::pastel_selection_theme());
+            }
+            ThemeName::PastelSelection => {
+                return Err(ThemeError::ThemeValidationError(
+                    "--colors requires a range theme".to_string(),
+                )
+                .into());
+            }
+            ThemeName::Oklch => return Ok(theme::oklch_palette_theme(args.colors)),
+        }
+    }
+
+    // Without a hue range the first hue band replaces the full hue range
+    let (hue_min, hue_max, hue_bands) =
+        match (args.hue_min, args.hue_max, args.hue_band.split_first()) {
+            (None, None, Some(((hue_min, hue_max), rest))) => (*hue_min, *hue_max, rest),
+            (hue_min, hue_max, _) => (
+                hue_min.unwrap_or(0.0),
+                hue_max.unwrap_or(360.0),
+                args.hue_band.as_slice(),
+            ),
+        };
+
+    // Missing values fall back to the default theme
+    let mut theme = HSLRange::new(
+        hue_min,
+        hue_max,
+        args.saturation_min.unwrap_or(50.0),
+        args.saturation_max.unwrap_or(75.0),
+        args.lightness_min.unwrap_or(60.0),
+        args.lightness_max.unwrap_or(70.0),
+        vec![RGB {
+            red: 240,
+            green: 240,
+            blue: 240,
+        }],
+    )?;
+    theme
+        .set_palette_size(args.colors)
+        .set_derived_background(match args.background {
+            BackgroundName::Gray => None,
+            BackgroundName::Complementary => Some(DerivedBackground::Complementary {
+                saturation: 40.0,
+                lightness: 88.0,
+            }),
+            BackgroundName::Analogous => Some(DerivedBackground::Analogous {
+                hue_offset: 30.0,
+                saturation: 40.0,
+                lightness: 88.0,
+            }),
+            BackgroundName::Tint => Some(DerivedBackground::Tint { lightness: 90.0 }),
+            BackgroundName::Shade => Some(DerivedBackground::Tint { lightness: 25.0 }),
+        });
+    for (hue_min, hue_max) in hue_bands {
+        theme.add_hue_band(*hue_min, *hue_max);
+    }
+    Ok(Arc::new(theme))
+}
+
+/// Unique file names for batch inputs.
+#[derive(Default)]
+struct FileNames {
+    /// The inputs which already have a file.
+    inputs: HashSet<String>,
+
+    /// The lowercased file names in use, so case insensitive file systems do not collide either.
+    used: HashSet<String>,
+}
+
+impl FileNames {
+    /// Gets a file name for the input which no earlier input uses, or `None` for repeated inputs.
+    ///
+    /// Inputs whose stems collide with an earlier file name get a `-2`, `-3`, ... suffix, and a
+    /// warning is printed.
+    fn file_name(&mut self, input: &str, extension: &str) -> Option<String> {
+        if !self.inputs.insert(input.to_string()) {
+            return None;
+        }
+
+        let stem = file_stem(input);
+        let mut file_name = format!("{stem}.{extension}");
+        let mut counter = 1;
+        while !self.used.insert(file_name.to_lowercase()) {
+            counter += 1;
+            file_name = format!("{stem}-{counter}.{extension}");
+        }
+        if counter > 1 {
+            eprintln!("warning: writing {input:?} to {file_name}, as its file name is taken");
+        }
+        Some(file_name)
+    }
+}
+
+/// Replaces characters that are not safe in file names.
+fn file_stem(input: &str) -> String {
+    input
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() || matches!(character, '-' | '_' | '.' | '@') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+    use identicon_rs::cell::CellGap;
+    use identicon_rs::renderer::ExportFormat;
+
+    use super::{
+        Args, FileNames, build_renderer, file_stem, format_from_path, parse_cell_gap,
+        parse_dimensions, parse_hue_band,
+    };
+
+    fn parse(args: &[&str]) -> Args {
+        Args::try_parse_from([&["identicon"], args].concat()).expect("arguments should parse")
+    }
+
+    #[test]
+    fn value_parsers_work() {
+        assert_eq!(Ok((5, 5)), parse_dimensions("5"));
+        assert_eq!(Ok((8, 4)), parse_dimensions("8x4"));
+        assert!(parse_dimensions("8x").is_err());
+
+        assert_eq!(Ok((330.0, 30.0)), parse_hue_band("330-30"));
+        assert!(parse_hue_band("330").is_err());
+
+        assert!(matches!(parse_cell_gap("10"), Ok(CellGap::Pixels(10))));
+        assert!(matches!(parse_cell_gap("0.1"), Ok(CellGap::Fraction(_))));
+        assert!(parse_cell_gap("wide").is_err());
+
+        assert_eq!(
+            Some(ExportFormat::Svg),
+            format_from_path(Path::new("icon.svg"))
+        );
+        assert_eq!(None, format_from_path(Path::new("icon")));
+    }
+
+    #[test]
+    fn arguments_are_validated() {
+        assert!(Args::try_parse_from(["identicon", "input", "--batch"]).is_err());
+        assert!(Args::try_parse_from(["identicon", "--size", "0x"]).is_err());
+        assert!(Args::try_parse_from(["identicon", "--theme", "unknown"]).is_err());
+    }
+
+    #[test]
+    fn renderer_uses_arguments() {
+        let renderer = build_renderer(&parse(&["input", "--size", "8x4", "--scale", "16x8"]))
+            .expect("renderer should build");
+        assert_eq!((8, 4), renderer.grid_dimensions());
+        assert_eq!((16, 8), renderer.scale_dimensions());
+
+        // The scale is smaller than the default size, so it must be set after the size
+        let renderer = build_renderer(&parse(&["input", "--size", "2", "--scale", "4"]))
+            .expect("renderer should build");
+        assert_eq!((2, 2), renderer.grid_dimensions());
+        assert_eq!((4, 4), renderer.scale_dimensions());
+
+        assert!(build_renderer(&parse(&["input", "--size", "8", "--scale", "4"])).is_err());
+    }
+
+    #[test]
+    fn theme_arguments_are_validated() {
+        for args in [
+            &["input", "--colors", "3"][..],
+            &["input", "--theme", "oklch", "--colors", "3"],
+            &["input", "--hue-min", "330", "--hue-max", "30"],
+            &["input", "--hue-band", "200-230", "--hue-band", "20-40"],
+            &["input", "--background", "complementary"],
+            &["input", "--min-contrast", "3"],
+        ] {
+            assert!(build_renderer(&parse(args)).is_ok(), "{args:?}");
+        }
+
+        for args in [
+            &["input", "--theme", "pastel-selection", "--colors", "3"][..],
+            &["input", "--theme", "oklch", "--hue-min", "10"],
+            &[
+                "input",
+                "--theme",
+                "pastel-selection",
+                "--background",
+                "tint",
+            ],
+            &["input", "--theme", "oklch", "--hue-band", "20-40"],
+            &["input", "--min-contrast", "30"],
+        ] {
+            assert!(build_renderer(&parse(args)).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn file_stem_replaces_unsafe_characters() {
+        assert_eq!("user@example.com", file_stem("user@example.com"));
+        assert_eq!("a_b_c", file_stem("a/b\\c"));
+        assert_eq!("hello_world", file_stem("hello world"));
+    }
+
+    #[test]
+    fn batch_file_names_are_unique() {
+        let mut file_names = FileNames::default();
+        let mut next = |input| file_names.file_name(input, "png");
+        assert_eq!(Some("a_b.png".to_string()), next("a/b"));
+        assert_eq!(Some("a_b-2.png".to_string()), next("a b"));
+        assert_eq!(Some("a_b-3.png".to_string()), next("a:b"));
+        assert_eq!(Some("A_B-4.png".to_string()), next("A B"));
+        assert_eq!(Some("a_b-2-2.png".to_string()), next("a_b-2"));
+
+        // Repeated inputs would write the same file again
+        assert_eq!(None, next("a b"));
+    }
+}
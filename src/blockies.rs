@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use crate::Identicon;
+use crate::color::{RGB, RGBA};
+use crate::hash::Hasher;
+use crate::pattern::Pattern;
+use crate::renderer::Renderer;
+use crate::symmetry::Symmetry;
+use crate::theme::Theme;
+use crate::theme::error::ThemeError;
+
+/// The grid size used by blockies.
+const SIZE: u32 = 8;
+
+/// The pixels per cell used by blockies.
+const SCALE: u32 = 4;
+
+/// The random draws used by the three colors, which come before the image data.
+const COLOR_DRAWS: usize = 3 * 6;
+
+/// Generates a new identicon compatible with the blockies of Ethereum wallets.
+///
+/// The result is an 8x8 grid scaled 4 times without a border, as blockies renders it by default.
+/// The border, scale and grid size can be changed afterwards like for any other identicon.
+///
+/// Blockies uses the seed as is, so Ethereum addresses should be lowercased by the caller.
+/// Like every input, the seed is trimmed.
+pub fn new(seed: &str) -> Identicon {
+    let mut identicon = Identicon {
+        input: String::new(),
+        hash: Vec::new(),
+        renderer: Renderer {
+            border: 0,
+            grid_width: SIZE,
+            grid_height: SIZE,
+            scale_width: SIZE * SCALE,
+            scale_height: SIZE * SCALE,
+            symmetry: Symmetry::VerticalCentred,
+            theme: Arc::new(BlockiesTheme),
+            hasher: Arc::new(BlockiesHasher),
+            pattern: Arc::new(BlockiesPattern),
+            ..Renderer::default()
+        },
+    };
+    identicon.set_input(seed);
+    identicon
+}
+
+/// Blockies hasher struct
+///
+/// Blockies seeds its random number generator with the input itself, so this returns the input
+/// bytes unchanged.
+///
+/// Implements [Hasher]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockiesHasher;
+
+impl Hasher for BlockiesHasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        input.to_vec()
+    }
+}
+
+/// Blockies theme struct
+///
+/// The main color, background color and spot color are drawn from the blockies random number
+/// generator seeded with the hash, which should come from [BlockiesHasher].
+/// The palette is the main color followed by the spot color.
+///
+/// Implements [Theme]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockiesTheme;
+
+impl Theme for BlockiesTheme {
+    fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        Ok(colors(hash)[0])
+    }
+
+    fn background_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        Ok(colors(hash)[1])
+    }
+
+    fn palette(&self, hash: &[u8]) -> Result<Vec<RGBA>, ThemeError> {
+        let [color, _, spot_color] = colors(hash);
+        Ok(vec![color.into(), spot_color.into()])
+    }
+}
+
+/// Blockies pattern struct
+///
+/// The left half of each row is drawn from the blockies random number generator seeded with the
+/// hash, which should come from [BlockiesHasher], and mirrored onto the right half.
+/// Cells are either the background, the main color or the spot color, which is palette index 2
+/// and falls back to the main color for single color palettes.
+///
+/// Implements [Pattern]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockiesPattern;
+
+impl Pattern for BlockiesPattern {
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+        self.palette_grid(hash, width, height, 1)
+            .into_iter()
+            .map(|index| index != 0)
+            .collect()
+    }
+
+    fn palette_grid(&self, hash: &[u8], width: u32, height: u32, colors: usize) -> Vec<usize> {
+        let mut random = Random::new(hash);
+        for _ in 0..COLOR_DRAWS {
+            random.next();
+        }
+
+        let data_width = width.div_ceil(2) as usize;
+        let mut grid = Vec::with_capacity((width * height) as usize);
+        for _ in 0..height {
+            let row: Vec<usize> = (0..data_width)
+                .map(|_| ((random.next() * 2.3).floor() as usize).min(colors))
+                .collect();
+            grid.extend((0..width as usize).map(|x| row[x.min(width as usize - 1 - x)]));
+        }
+        grid
+    }
+}
+
+/// The xorshift random number generator of blockies.
+///
+/// Blockies runs on JavaScript numbers, so the seed is kept wider than 32 bits until the bitwise
+/// operators truncate it like `ToInt32` does.
+struct Random {
+    state: [i64; 4],
+}
+
+impl Random {
+    fn new(seed: &[u8]) -> Random {
+        let mut state = [0i64; 4];
+        // Blockies seeds from UTF-16 code units
+        for (i, unit) in String::from_utf8_lossy(seed).encode_utf16().enumerate() {
+            let value = state[i % 4];
+            state[i % 4] = ((value as i32).wrapping_shl(5) as i64) - value + unit as i64;
+        }
+        Random { state }
+    }
+
+    /// Returns the next value between 0.0 and 1.0.
+    ///
+    /// The arithmetic shifts cancel out the sign bit, so blockies never reaches 1.0 even though it
+    /// divides by 2^31.
+    fn next(&mut self) -> f64 {
+        let first = self.state[0] as i32;
+        let t = first ^ first.wrapping_shl(11);
+        self.state.rotate_left(1);
+        let last = self.state[2] as i32;
+        let next = last ^ (last >> 19) ^ t ^ (t >> 8);
+        self.state[3] = next as i64;
+        next as u32 as f64 / 2_147_483_648.0
+    }
+
+    /// Returns the next color as a CSS `hsl()` color would be rendered by a browser.
+    fn next_color(&mut self) -> RGB {
+        let hue = (self.next() * 360.0).floor();
+        let saturation = self.next() * 60.0 + 40.0;
+        let lightness = (self.next() + self.next() + self.next() + self.next()) * 25.0;
+        css_hsl_to_rgb(hue, saturation, lightness)
+    }
+}
+
+/// Returns the main color, background color and spot color in blockies order.
+fn colors(hash: &[u8]) -> [RGB; 3] {
+    let mut random = Random::new(hash);
+    [
+        random.next_color(),
+        random.next_color(),
+        random.next_color(),
+    ]
+}
+
+/// Converts a CSS `hsl()` color to RGB following CSS Color 4.
+///
+/// This uses full precision and rounding instead of [`HSLRange`](crate::theme::HSLRange)'s
+/// conversion, so the colors match the browser exactly.
+fn css_hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> RGB {
+    let saturation = saturation / 100.0;
+    let lightness = lightness / 100.0;
+    let a = saturation * lightness.min(1.0 - lightness);
+    let channel = |n: f64| {
+        let k = (n + hue / 30.0) % 12.0;
+        let value = lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        (value * 255.0).round() as u8
+    };
+    RGB::from((channel(0.0), channel(8.0), channel(4.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use image::GenericImageView;
+
+    use crate::blockies::{self, BlockiesPattern, BlockiesTheme};
+    use crate::color::RGB;
+    use crate::pattern::Pattern;
+    use crate::theme::Theme;
+
+    /// A seed with its main, background and spot colors and its rows of palette indices.
+    type Reference = (&'static str, [(u8, u8, u8); 3], [&'static str; 8]);
+
+    // Reference values from the blockies JavaScript implementation
+    const REFERENCES: [Reference; 4] = [
+        (
+            "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359",
+            [(250, 173, 21), (231, 237, 51), (115, 105, 248)],
+            [
+                "10000001", "00000000", "11000011", "12111121", "01011010", "02122120", "02000020",
+                "10211201",
+            ],
+        ),
+        (
+            "0x0000000000000000000000000000000000000000",
+            [(218, 69, 84), (44, 118, 209), (200, 123, 165)],
+            [
+                "00100100", "10000001", "01100110", "21111112", "10122101", "10011001", "20000002",
+                "00211200",
+            ],
+        ),
+        (
+            "hello",
+            [(65, 28, 28), (80, 62, 149), (131, 87, 202)],
+            [
+                "01000010", "00022000", "11000011", "00200200", "11022011", "10200201", "11000011",
+                "10011001",
+            ],
+        ),
+        (
+            "héllo wörld ✓",
+            [(243, 17, 111), (11, 47, 82), (168, 43, 184)],
+            [
+                "01000010", "10000001", "11011011", "10100101", "01011010", "10000001", "01000010",
+                "00200200",
+            ],
+        ),
+    ];
+
+    #[test]
+    fn blockies_matches_reference() {
+        for (seed, [color, background, spot_color], rows) in REFERENCES {
+            let hash = seed.as_bytes();
+            assert_eq!(Some(RGB::from(color)), BlockiesTheme.main_color(hash).ok());
+            assert_eq!(
+                Some(RGB::from(background)),
+                BlockiesTheme.background_color(hash).ok()
+            );
+            assert_eq!(
+                Some(vec![RGB::from(color).into(), RGB::from(spot_color).into()]),
+                BlockiesTheme.palette(hash).ok()
+            );
+
+            let expected: Vec<usize> = rows
+                .concat()
+                .bytes()
+                .map(|cell| (cell - b'0') as usize)
+                .collect();
+            assert_eq!(expected, BlockiesPattern.palette_grid(hash, 8, 8, 2));
+        }
+    }
+
+    #[test]
+    fn single_color_palette_uses_main_color() {
+        let hash = b"hello";
+        let expected: Vec<usize> = BlockiesPattern
+            .palette_grid(hash, 8, 8, 2)
+            .into_iter()
+            .map(|index| index.min(1))
+            .collect();
+        assert_eq!(expected, BlockiesPattern.palette_grid(hash, 8, 8, 1));
+    }
+
+    #[test]
+    fn blockies_identicon_works() {
+        let (seed, [color, background, spot_color], rows) = REFERENCES[0];
+        let identicon = blockies::new(seed);
+        let image = identicon.generate_image().expect("blockies should render");
+        assert_eq!((32, 32), image.dimensions());
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.bytes().enumerate() {
+                let expected = match cell {
+                    b'0' => background,
+                    b'1' => color,
+                    _ => spot_color,
+                };
+                let pixel = image.get_pixel(x as u32 * 4 + 1, y as u32 * 4 + 2);
+                assert_eq!([expected.0, expected.1, expected.2, 255], pixel.0);
+            }
+        }
+    }
+}
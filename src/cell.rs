@@ -0,0 +1,339 @@
+use crate::grid;
+use crate::symmetry::Symmetry;
+
+/// Shape drawn for every active cell of the grid.
+///
+/// Shapes other than [`CellStyle::Square`] are anti-aliased in raster output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellStyle {
+    /// A square covering the full cell.
+    ///
+    /// This is the default.
+    #[default]
+    Square,
+
+    /// A circle touching the edges of the cell.
+    Circle,
+
+    /// A square with its corners rounded by a quarter of the cell size.
+    RoundedSquare,
+
+    /// A diamond with its corners at the middle of the cell edges.
+    Diamond,
+
+    /// A triangle pointing up, right, down or left, with its base along the opposite cell edge.
+    ///
+    /// Each direction is picked by two bits of the SHAKE256 extension of the hash, and is mirrored
+    /// or rotated along with the cell by the [`Symmetry`].
+    Triangle,
+}
+
+/// Spacing left between neighbouring cells, filled with the background color.
+///
+/// The gap is only placed between cells, so the outer cells still reach the border and the image
+/// size does not change.
+/// It is limited so every cell keeps at least one pixel.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellGap {
+    /// A gap of a number of pixels.
+    Pixels(u32),
+
+    /// A gap of a fraction of the cell size, e.g. `0.1` for a tenth of a cell.
+    ///
+    /// The gap is rounded to the nearest pixel, and negative fractions are treated as no gap.
+    Fraction(f32),
+}
+
+impl Default for CellGap {
+    fn default() -> Self {
+        CellGap::Pixels(0)
+    }
+}
+
+impl CellGap {
+    /// Gets the gap in pixels between `cells` cells spread over `length` pixels.
+    pub(crate) fn pixels(&self, cells: u32, length: u32) -> u32 {
+        let gap = match self {
+            CellGap::Pixels(pixels) => *pixels,
+            CellGap::Fraction(fraction) => {
+                (fraction.max(0.0) * length as f32 / cells.max(1) as f32).round() as u32
+            }
+        };
+
+        // Every cell must keep at least one pixel
+        let max_gap = length.saturating_sub(cells) / cells.saturating_sub(1).max(1);
+        gap.min(max_gap)
+    }
+
+    /// Scales the pixel lengths of the gap.
+    pub(crate) fn resize(&self, resize: impl Fn(u32) -> u32) -> CellGap {
+        match self {
+            CellGap::Pixels(pixels) => CellGap::Pixels(resize(*pixels)),
+            gap => *gap,
+        }
+    }
+}
+
+/// The direction a triangle cell points to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, in the order they are picked by the hash bits.
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Gets the unit vector of the direction, where `y` points down.
+    fn vector(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    /// Gets the direction of a unit vector, where `y` points down.
+    fn from_vector(vector: (i32, i32)) -> Direction {
+        match vector {
+            (1, 0) => Direction::Right,
+            (0, 1) => Direction::Down,
+            (-1, 0) => Direction::Left,
+            _ => Direction::Up,
+        }
+    }
+}
+
+impl CellStyle {
+    /// Checks if the point at `u`, `v` is within the shape, where the cell spans from 0 to 1.
+    pub(crate) fn contains(&self, u: f32, v: f32, direction: Direction) -> bool {
+        match self {
+            CellStyle::Square => true,
+            CellStyle::Circle => (u - 0.5).powi(2) + (v - 0.5).powi(2) <= 0.25,
+            CellStyle::RoundedSquare => {
+                let corner_u = ((u - 0.5).abs() - 0.25).max(0.0);
+                let corner_v = ((v - 0.5).abs() - 0.25).max(0.0);
+                corner_u.powi(2) + corner_v.powi(2) <= 0.0625
+            }
+            CellStyle::Diamond => (u - 0.5).abs() + (v - 0.5).abs() <= 0.5,
+            CellStyle::Triangle => {
+                // Turn the point so the triangle points up
+                let (u, v) = match direction {
+                    Direction::Up => (u, v),
+                    Direction::Right => (v, 1.0 - u),
+                    Direction::Down => (u, 1.0 - v),
+                    Direction::Left => (v, u),
+                };
+                (u - 0.5).abs() <= v / 2.0
+            }
+        }
+    }
+
+    /// Gets the SVG element drawing the shape within the `width` x `height` cell at `x`, `y`.
+    pub(crate) fn svg_shape(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        direction: Direction,
+    ) -> String {
+        let (left, top) = (x as f32, y as f32);
+        let (right, bottom) = ((x + width) as f32, (y + height) as f32);
+        let (centre_x, centre_y) = ((left + right) / 2.0, (top + bottom) / 2.0);
+        let polygon = |points: [(f32, f32); 3]| {
+            let points = points
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(r#"<polygon points="{points}"/>"#)
+        };
+
+        match self {
+            CellStyle::Square => {
+                format!(r#"<rect x="{x}" y="{y}" width="{width}" height="{height}"/>"#)
+            }
+            CellStyle::Circle => format!(
+                r#"<ellipse cx="{centre_x}" cy="{centre_y}" rx="{}" ry="{}"/>"#,
+                width as f32 / 2.0,
+                height as f32 / 2.0
+            ),
+            CellStyle::RoundedSquare => format!(
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" rx="{}" ry="{}"/>"#,
+                width as f32 / 4.0,
+                height as f32 / 4.0
+            ),
+            CellStyle::Diamond => format!(
+                r#"<polygon points="{centre_x},{top} {right},{centre_y} {centre_x},{bottom} {left},{centre_y}"/>"#
+            ),
+            CellStyle::Triangle => match direction {
+                Direction::Up => polygon([(centre_x, top), (right, bottom), (left, bottom)]),
+                Direction::Right => polygon([(right, centre_y), (left, bottom), (left, top)]),
+                Direction::Down => polygon([(centre_x, bottom), (left, top), (right, top)]),
+                Direction::Left => polygon([(left, centre_y), (right, top), (right, bottom)]),
+            },
+        }
+    }
+}
+
+/// Picks the direction of every triangle cell of a `width` x `height` grid.
+///
+/// The direction of a cell is taken from its source cell, then mirrored or rotated the same way as
+/// the cell, so the directions follow the symmetry of the grid.
+/// Source cells on a mirror axis only point along that axis, so they stay symmetric themselves.
+/// No direction is symmetric at the centre of a rotation, so those cells point anywhere.
+pub(crate) fn triangle_directions(
+    hash: &[u8],
+    width: u32,
+    height: u32,
+    symmetry: Symmetry,
+) -> Vec<Direction> {
+    // Use the bits following the hash, so they are independent of the grid
+    let cells = (width * height) as usize;
+    let bytes = grid::extend_hash(hash, hash.len() + cells.div_ceil(4));
+    let bits = &bytes[hash.len()..];
+
+    (0..width * height)
+        .map(|location| {
+            let ((x, y), transform) =
+                symmetry.source(location % width, location / width, width, height);
+            let source = (x + y * width) as usize;
+
+            // Keep the directions that are not changed by the transforms keeping the source cell
+            let keeping = symmetry
+                .transforms(width, height)
+                .iter()
+                .filter(|transform| transform.apply(x, y, width, height) == (x, y));
+            let mut candidates = Direction::ALL
+                .into_iter()
+                .filter(|direction| {
+                    keeping.clone().all(|transform| {
+                        transform.apply_direction(direction.vector()) == direction.vector()
+                    })
+                })
+                .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                candidates = Direction::ALL.to_vec();
+            }
+
+            let bits = (bits[source / 4] >> (2 * (source % 4))) & 0b11;
+            let direction = candidates[bits as usize % candidates.len()];
+            Direction::from_vector(transform.inverse().apply_direction(direction.vector()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::{CellGap, CellStyle, Direction, triangle_directions};
+    use crate::hash::hash_value;
+    use crate::symmetry::Symmetry;
+
+    #[test]
+    fn shapes_work() {
+        for style in [
+            CellStyle::Square,
+            CellStyle::Circle,
+            CellStyle::RoundedSquare,
+            CellStyle::Diamond,
+        ] {
+            assert!(style.contains(0.5, 0.5, Direction::Up));
+        }
+        assert!(CellStyle::Square.contains(0.0, 0.0, Direction::Up));
+        assert!(!CellStyle::Circle.contains(0.1, 0.1, Direction::Up));
+        assert!(CellStyle::RoundedSquare.contains(0.1, 0.1, Direction::Up));
+        assert!(!CellStyle::RoundedSquare.contains(0.01, 0.01, Direction::Up));
+        assert!(!CellStyle::Diamond.contains(0.2, 0.2, Direction::Up));
+    }
+
+    #[test]
+    fn triangles_point_in_their_direction() {
+        let style = CellStyle::Triangle;
+        assert!(style.contains(0.5, 0.1, Direction::Up));
+        assert!(!style.contains(0.1, 0.1, Direction::Up));
+        assert!(style.contains(0.9, 0.5, Direction::Right));
+        assert!(!style.contains(0.9, 0.1, Direction::Right));
+        assert!(style.contains(0.5, 0.9, Direction::Down));
+        assert!(!style.contains(0.1, 0.9, Direction::Down));
+        assert!(style.contains(0.1, 0.5, Direction::Left));
+        assert!(!style.contains(0.1, 0.1, Direction::Left));
+    }
+
+    #[test]
+    fn cell_gap_pixels_work() {
+        assert_eq!(10, CellGap::Pixels(10).pixels(5, 500));
+        assert_eq!(10, CellGap::Fraction(0.1).pixels(5, 500));
+        assert_eq!(0, CellGap::Fraction(-0.1).pixels(5, 500));
+        assert_eq!(0, CellGap::Fraction(f32::NAN).pixels(5, 500));
+
+        // Every cell keeps at least one pixel
+        assert_eq!(123, CellGap::Pixels(1000).pixels(5, 500));
+        assert_eq!(0, CellGap::Pixels(1000).pixels(5, 5));
+        assert_eq!(
+            CellGap::Pixels(5),
+            CellGap::Pixels(10).resize(|length| length / 2)
+        );
+    }
+
+    #[test]
+    fn svg_shapes_work() {
+        assert_eq!(
+            r#"<rect x="10" y="20" width="30" height="40"/>"#,
+            CellStyle::Square.svg_shape(10, 20, 30, 40, Direction::Up)
+        );
+        assert_eq!(
+            r#"<ellipse cx="25" cy="40" rx="15" ry="20"/>"#,
+            CellStyle::Circle.svg_shape(10, 20, 30, 40, Direction::Up)
+        );
+        assert_eq!(
+            r#"<rect x="10" y="20" width="30" height="40" rx="7.5" ry="10"/>"#,
+            CellStyle::RoundedSquare.svg_shape(10, 20, 30, 40, Direction::Up)
+        );
+        assert_eq!(
+            r#"<polygon points="25,20 40,40 25,60 10,40"/>"#,
+            CellStyle::Diamond.svg_shape(10, 20, 30, 40, Direction::Up)
+        );
+        assert_eq!(
+            r#"<polygon points="10,40 40,20 40,60"/>"#,
+            CellStyle::Triangle.svg_shape(10, 20, 30, 40, Direction::Left)
+        );
+    }
+
+    #[test]
+    fn triangle_directions_follow_symmetry() {
+        let hash = hash_value("test");
+        let directions = triangle_directions(&hash, 5, 5, Symmetry::Vertical);
+        for y in 0..5 {
+            for x in 0..5 {
+                let direction = directions[x + y * 5];
+                let mirrored = directions[(4 - x) + y * 5];
+                let expected = match direction {
+                    Direction::Left => Direction::Right,
+                    Direction::Right => Direction::Left,
+                    direction => direction,
+                };
+                assert_eq!(expected, mirrored);
+            }
+        }
+
+        // The directions are not all the same
+        let directions = triangle_directions(&hash, 5, 5, Symmetry::None);
+        assert!(
+            directions
+                .iter()
+                .any(|direction| *direction != directions[0])
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::color::{RGB, srgb_to_linear};
+
+/// Contrast Metric Enum
+///
+/// The metric used to measure the contrast between a color and its background.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContrastMetric {
+    /// The WCAG 2.x contrast ratio, between 1.0 for equal colors and 21.0 for black on white.
+    ///
+    /// WCAG asks for a ratio of at least 3.0 for graphical objects.
+    ///
+    /// This is the default.
+    #[default]
+    Wcag2,
+
+    /// The APCA lightness contrast, between 0.0 for equal colors and about 108.0 for white on
+    /// black.
+    ///
+    /// APCA contrast depends on which color is in front, so the color is measured as text on the
+    /// background, and the polarity is ignored.
+    /// An Lc of 45 or more is recommended for large graphical objects.
+    Apca,
+}
+
+impl ContrastMetric {
+    /// Measures the contrast of `color` on `background` with this metric.
+    ///
+    /// Larger values have more contrast.
+    pub fn contrast(&self, color: RGB, background: RGB) -> f32 {
+        match self {
+            ContrastMetric::Wcag2 => wcag_contrast_ratio(color, background),
+            ContrastMetric::Apca => apca_contrast(color, background).abs(),
+        }
+    }
+
+    /// The largest contrast possible with this metric.
+    pub fn max_contrast(&self) -> f32 {
+        match self {
+            ContrastMetric::Wcag2 => 21.0,
+            ContrastMetric::Apca => {
+                apca_contrast(RGB::from((255, 255, 255)), RGB::from((0, 0, 0))).abs()
+            }
+        }
+    }
+}
+
+/// Returns the WCAG 2.x relative luminance of a color, between 0.0 for black and 1.0 for white.
+pub fn relative_luminance(color: RGB) -> f32 {
+    let component = |value: u8| srgb_to_linear(value as f32 / 255.0);
+    0.2126 * component(color.red) + 0.7152 * component(color.green) + 0.0722 * component(color.blue)
+}
+
+/// Returns the WCAG 2.x contrast ratio between two colors, between 1.0 and 21.0.
+///
+/// The ratio is symmetric, so the order of the colors does not matter.
+pub fn wcag_contrast_ratio(first: RGB, second: RGB) -> f32 {
+    let (first, second) = (relative_luminance(first), relative_luminance(second));
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// Returns the APCA lightness contrast (Lc) of `text` on `background`, following APCA-W3 0.0.98G.
+///
+/// The result is positive for dark text on a light background, negative for light text on a dark
+/// background, and 0.0 for colors too close to tell apart.
+pub fn apca_contrast(text: RGB, background: RGB) -> f32 {
+    let (text, background) = (apca_luminance(text), apca_luminance(background));
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+
+    let contrast = if background > text {
+        let contrast = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+        if contrast < 0.1 {
+            0.0
+        } else {
+            contrast - 0.027
+        }
+    } else {
+        let contrast = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+        if contrast > -0.1 {
+            0.0
+        } else {
+            contrast + 0.027
+        }
+    };
+    contrast * 100.0
+}
+
+/// Returns the APCA screen luminance of a color, with the soft clamp for near black colors.
+fn apca_luminance(color: RGB) -> f32 {
+    let component = |value: u8| (value as f32 / 255.0).powf(2.4);
+    let luminance = 0.212_672_9 * component(color.red)
+        + 0.715_152_2 * component(color.green)
+        + 0.072_175 * component(color.blue);
+    if luminance < 0.022 {
+        luminance + (0.022 - luminance).powf(1.414)
+    } else {
+        luminance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{
+        ContrastMetric, RGB, apca_contrast, relative_luminance, wcag_contrast_ratio,
+    };
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn wcag_matches_reference() {
+        let (black, white) = (RGB::from((0, 0, 0)), RGB::from((255, 255, 255)));
+        assert_close(0.0, relative_luminance(black));
+        assert_close(1.0, relative_luminance(white));
+        assert_close(21.0, wcag_contrast_ratio(black, white));
+        assert_close(21.0, wcag_contrast_ratio(white, black));
+        assert_close(1.0, wcag_contrast_ratio(white, white));
+
+        // #767676 is the lightest gray passing WCAG AA on white
+        assert_close(4.54, wcag_contrast_ratio(RGB::from((118, 118, 118)), white));
+    }
+
+    #[test]
+    fn apca_matches_reference() {
+        // Reference values from the APCA-W3 documentation
+        let (black, white) = (RGB::from((0, 0, 0)), RGB::from((255, 255, 255)));
+        let gray = RGB::from((136, 136, 136));
+        assert_close(106.04, apca_contrast(black, white));
+        assert_close(-107.88, apca_contrast(white, black));
+        assert_close(63.06, apca_contrast(gray, white));
+        assert_close(-68.54, apca_contrast(white, gray));
+        assert_close(0.0, apca_contrast(gray, gray));
+
+        assert_close(107.88, ContrastMetric::Apca.max_contrast());
+        assert_close(68.54, ContrastMetric::Apca.contrast(white, gray));
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// Color Errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ColorError {
+    /// Failed to parse a color string
+    #[error("could not parse color {input:?}: {reason}")]
+    ColorParseError {
+        /// The color string that failed to parse.
+        input: String,
+        /// A description of what failed to parse.
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::error::ColorError;
+
+    #[test]
+    fn color_parse_error_works() {
+        let error = ColorError::ColorParseError {
+            input: "#12".to_string(),
+            reason: "hex colors must have 3 or 6 digits".to_string(),
+        };
+        let expected_text = r##"could not parse color "#12": hex colors must have 3 or 6 digits"##;
+        assert_eq!(expected_text, error.to_string());
+    }
+}
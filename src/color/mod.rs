@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use error::ColorError;
+
+/// Color Errors
+///
+/// Identicon Errors can wrap these errors
+pub mod error;
+
+mod contrast;
+mod named;
+mod parse;
+mod space;
+
+pub use contrast::{ContrastMetric, apca_contrast, relative_luminance, wcag_contrast_ratio};
+pub use space::{Oklab, Oklch, linear_to_srgb, srgb_to_linear};
+
+/// RGB Color Struct
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RGB {
+    /// The RGB Red Value
+    pub red: u8,
+
+    /// The RGB Green Value
+    pub green: u8,
+
+    /// The RGB Blue Value
+    pub blue: u8,
+}
+
+impl RGB {
+    /// Formats the color as a lowercase `#rrggbb` hex string.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// Formats the color as a lowercase `#rrggbb` hex string.
+impl fmt::Display for RGB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Parses a CSS color string.
+///
+/// The supported formats are:
+/// - `#rgb` and `#rrggbb` hex colors
+/// - `rgb(r, g, b)`, with values between 0 and 255 or percentages
+/// - `hsl(h, s%, l%)`, with the hue in degrees
+/// - CSS named colors, such as `rebeccapurple`
+///
+/// Parsing is case insensitive and ignores surrounding whitespace.
+impl FromStr for RGB {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_color(s)
+    }
+}
+
+impl TryFrom<&str> for RGB {
+    type Error = ColorError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<(u8, u8, u8)> for RGB {
+    fn from(value: (u8, u8, u8)) -> Self {
+        RGB {
+            red: value.0,
+            green: value.1,
+            blue: value.2,
+        }
+    }
+}
+
+/// RGBA Color Struct
+///
+/// This is an [RGB] color with an additional alpha channel, where an alpha of 0 is fully
+/// transparent and an alpha of 255 is fully opaque.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RGBA {
+    /// The RGBA Red Value
+    pub red: u8,
+
+    /// The RGBA Green Value
+    pub green: u8,
+
+    /// The RGBA Blue Value
+    pub blue: u8,
+
+    /// The RGBA Alpha Value
+    pub alpha: u8,
+}
+
+impl RGBA {
+    /// Returns true if the color is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        self.alpha == u8::MAX
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for RGBA {
+    fn from(value: (u8, u8, u8, u8)) -> Self {
+        RGBA {
+            red: value.0,
+            green: value.1,
+            blue: value.2,
+            alpha: value.3,
+        }
+    }
+}
+
+impl From<RGB> for RGBA {
+    fn from(value: RGB) -> Self {
+        RGBA {
+            red: value.red,
+            green: value.green,
+            blue: value.blue,
+            alpha: u8::MAX,
+        }
+    }
+}
+
+impl From<RGBA> for RGB {
+    fn from(value: RGBA) -> Self {
+        RGB {
+            red: value.red,
+            green: value.green,
+            blue: value.blue,
+        }
+    }
+}
+
+/// Converts an HSL color to RGB components between 0.0 and 1.0.
+///
+/// `hue` is expected to be between 0.0 and 360.0.
+/// `saturation` and `lightness` are expected to be between 0.0 and 1.0.
+pub(crate) fn hsl_to_rgb_components(hue: f32, saturation: f32, lightness: f32) -> (f32, f32, f32) {
+    let chroma = (1.0 - ((2.0 * lightness) - 1.0).abs()) * saturation;
+    let hue_prime = hue / 60.0;
+    let x = chroma * (1.0 - ((hue_prime % 2.0) - 1.0).abs());
+
+    // Get Prime RGB Values
+    let (r_prime, g_prime, b_prime) = match hue_prime {
+        0.0..1.0 => (chroma, x, 0.0),
+        1.0..2.0 => (x, chroma, 0.0),
+        2.0..3.0 => (0.0, chroma, x),
+        3.0..4.0 => (0.0, x, chroma),
+        4.0..5.0 => (x, 0.0, chroma),
+        5.0..=6.0 => (chroma, 0.0, x),
+        // This should not occur as the hue is between 0 and 360, which casts down to between 0-6
+        _ => (0.0, 0.0, 0.0),
+    };
+
+    // Lightness modifier
+    let m = lightness - chroma * 0.5;
+
+    (r_prime + m, g_prime + m, b_prime + m)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{RGB, RGBA};
+
+    #[test]
+    fn hex_formatting_works() {
+        let color = RGB::from((1, 171, 255));
+        assert_eq!("#01abff", color.to_hex());
+        assert_eq!("#01abff", color.to_string());
+        assert_eq!(Ok(color), color.to_hex().parse());
+        assert_eq!(Ok(color), RGB::try_from("#01ABFF"));
+    }
+
+    #[test]
+    fn rgb_to_rgba_is_opaque() {
+        let color: RGBA = RGB::from((1, 2, 3)).into();
+        assert_eq!(RGBA::from((1, 2, 3, 255)), color);
+        assert!(color.is_opaque());
+    }
+
+    #[test]
+    fn rgba_to_rgb_drops_alpha() {
+        let color: RGB = RGBA::from((1, 2, 3, 0)).into();
+        assert_eq!(RGB::from((1, 2, 3)), color);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::color::{RGB, error::ColorError, hsl_to_rgb_components, named::NAMED_COLORS};
+
+/// Parses a CSS color string.
+///
+/// Supports `#rgb`, `#rrggbb`, `rgb(...)`, `hsl(...)`, and CSS named colors.
+pub(crate) fn parse_color(input: &str) -> Result<RGB, ColorError> {
+    let value = input.trim().to_ascii_lowercase();
+
+    let result = if let Some(hex) = value.strip_prefix('#') {
+        parse_hex(hex)
+    } else if let Some(arguments) = function_arguments(&value, "rgb") {
+        parse_rgb_function(&arguments)
+    } else if let Some(arguments) = function_arguments(&value, "hsl") {
+        parse_hsl_function(&arguments)
+    } else {
+        NAMED_COLORS
+            .binary_search_by_key(&value.as_str(), |(name, _)| name)
+            .map(|index| NAMED_COLORS[index].1.into())
+            .map_err(|_| "unknown color name".to_string())
+    };
+
+    result.map_err(|reason| ColorError::ColorParseError {
+        input: input.to_string(),
+        reason,
+    })
+}
+
+fn parse_hex(hex: &str) -> Result<RGB, String> {
+    if !hex.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err("hex colors must only contain hex digits".to_string());
+    }
+
+    let digit = |index: usize| u8::from_str_radix(&hex[index..=index], 16).unwrap_or_default();
+    match hex.len() {
+        3 => Ok(RGB {
+            red: digit(0) * 17,
+            green: digit(1) * 17,
+            blue: digit(2) * 17,
+        }),
+        6 => Ok(RGB {
+            red: digit(0) * 16 + digit(1),
+            green: digit(2) * 16 + digit(3),
+            blue: digit(4) * 16 + digit(5),
+        }),
+        _ => Err("hex colors must have 3 or 6 digits".to_string()),
+    }
+}
+
+/// Gets the three arguments of a CSS function, such as `rgb(1, 2, 3)` or `rgb(1 2 3)`.
+fn function_arguments(value: &str, name: &str) -> Option<Vec<String>> {
+    let arguments = value.strip_prefix(name)?.trim_start();
+    let arguments = arguments.strip_prefix('(')?.strip_suffix(')')?;
+
+    let arguments = if arguments.contains(',') {
+        arguments.split(',').map(str::trim).collect::<Vec<_>>()
+    } else {
+        arguments.split_whitespace().collect()
+    };
+    Some(arguments.into_iter().map(str::to_string).collect())
+}
+
+fn parse_rgb_function(arguments: &[String]) -> Result<RGB, String> {
+    let [red, green, blue] = arguments else {
+        return Err("rgb() must have 3 arguments".to_string());
+    };
+
+    let component = |name: &str, value: &str| -> Result<u8, String> {
+        let (value, max) = match value.strip_suffix('%') {
+            Some(percent) => (percent, 100.0),
+            None => (value, 255.0),
+        };
+        let value: f32 = value
+            .parse()
+            .map_err(|_| format!("{name} must be a number"))?;
+        if (0.0..=max).contains(&value) {
+            Ok((value / max * 255.0).round() as u8)
+        } else {
+            Err(format!("{name} must be between 0 and {max}"))
+        }
+    };
+
+    Ok(RGB {
+        red: component("red", red)?,
+        green: component("green", green)?,
+        blue: component("blue", blue)?,
+    })
+}
+
+fn parse_hsl_function(arguments: &[String]) -> Result<RGB, String> {
+    let [hue, saturation, lightness] = arguments else {
+        return Err("hsl() must have 3 arguments".to_string());
+    };
+
+    let hue: f32 = hue
+        .strip_suffix("deg")
+        .unwrap_or(hue)
+        .parse()
+        .map_err(|_| "hue must be a number".to_string())?;
+
+    let percent = |name: &str, value: &str| -> Result<f32, String> {
+        let value: f32 = value
+            .strip_suffix('%')
+            .unwrap_or(value)
+            .parse()
+            .map_err(|_| format!("{name} must be a number"))?;
+        if (0.0..=100.0).contains(&value) {
+            Ok(value / 100.0)
+        } else {
+            Err(format!("{name} must be between 0 and 100"))
+        }
+    };
+
+    let (red, green, blue) = hsl_to_rgb_components(
+        hue.rem_euclid(360.0),
+        percent("saturation", saturation)?,
+        percent("lightness", lightness)?,
+    );
+    Ok(RGB {
+        red: (red * 255.0).round() as u8,
+        green: (green * 255.0).round() as u8,
+        blue: (blue * 255.0).round() as u8,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{RGB, named::NAMED_COLORS, parse::parse_color};
+
+    fn parse(input: &str) -> RGB {
+        parse_color(input).expect("color should parse")
+    }
+
+    #[test]
+    fn hex_works() {
+        assert_eq!(RGB::from((0x12, 0xab, 0xef)), parse("#12abef"));
+        assert_eq!(RGB::from((0x12, 0xab, 0xef)), parse("  #12ABEF "));
+        assert_eq!(RGB::from((0x11, 0xaa, 0xff)), parse("#1af"));
+    }
+
+    #[test]
+    fn hex_failure_works() {
+        assert!(parse_color("#12").is_err());
+        assert!(parse_color("#1234567").is_err());
+        assert!(parse_color("#12345g").is_err());
+        assert!(parse_color("#").is_err());
+    }
+
+    #[test]
+    fn rgb_works() {
+        assert_eq!(RGB::from((1, 2, 3)), parse("rgb(1, 2, 3)"));
+        assert_eq!(RGB::from((1, 2, 3)), parse("RGB(1 2 3)"));
+        assert_eq!(RGB::from((255, 128, 0)), parse("rgb(100%, 50%, 0%)"));
+    }
+
+    #[test]
+    fn rgb_failure_works() {
+        assert!(parse_color("rgb(1, 2)").is_err());
+        assert!(parse_color("rgb(1, 2, 256)").is_err());
+        assert!(parse_color("rgb(1, 2, x)").is_err());
+        assert!(parse_color("rgb(1, 2, 3").is_err());
+    }
+
+    #[test]
+    fn hsl_works() {
+        assert_eq!(RGB::from((255, 0, 0)), parse("hsl(0, 100%, 50%)"));
+        assert_eq!(RGB::from((0, 255, 0)), parse("hsl(120deg 100% 50%)"));
+        assert_eq!(RGB::from((0, 0, 255)), parse("hsl(-120, 100, 50)"));
+        assert_eq!(RGB::from((128, 128, 128)), parse("hsl(0, 0%, 50%)"));
+    }
+
+    #[test]
+    fn hsl_failure_works() {
+        assert!(parse_color("hsl(0, 101%, 50%)").is_err());
+        assert!(parse_color("hsl(x, 100%, 50%)").is_err());
+    }
+
+    #[test]
+    fn named_works() {
+        assert_eq!(RGB::from((102, 51, 153)), parse("rebeccapurple"));
+        assert_eq!(RGB::from((0, 128, 0)), parse("Green"));
+        assert!(parse_color("notacolor").is_err());
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
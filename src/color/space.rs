@@ -0,0 +1,245 @@
+use crate::color::RGB;
+
+/// The chroma precision of [Oklch::clamp_chroma].
+const CHROMA_PRECISION: f32 = 0.0001;
+
+/// Oklab Color Struct
+///
+/// Oklab is a perceptual color space, where the same lightness looks equally bright for every
+/// hue. See <https://bottosson.github.io/posts/oklab/>.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Oklab {
+    /// The perceived lightness, between 0.0 for black and 1.0 for white
+    pub lightness: f32,
+
+    /// The green to red axis
+    pub a: f32,
+
+    /// The blue to yellow axis
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Converts linear sRGB components between 0.0 and 1.0 to Oklab.
+    pub fn from_linear_srgb(red: f32, green: f32, blue: f32) -> Oklab {
+        let l = 0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue;
+        let m = 0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue;
+        let s = 0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue;
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+        Oklab {
+            lightness: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        }
+    }
+
+    /// Converts the color to linear sRGB components.
+    ///
+    /// Colors outside of the sRGB gamut have components below 0.0 or above 1.0.
+    pub fn to_linear_srgb(&self) -> (f32, f32, f32) {
+        let l = self.lightness + 0.396_337_78 * self.a + 0.215_803_76 * self.b;
+        let m = self.lightness - 0.105_561_346 * self.a - 0.063_854_17 * self.b;
+        let s = self.lightness - 0.089_484_18 * self.a - 1.291_485_5 * self.b;
+        let (l, m, s) = (l.powi(3), m.powi(3), s.powi(3));
+
+        (
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        )
+    }
+
+    /// Checks if the color is within the sRGB gamut, allowing for rounding to 8 bit components.
+    pub fn in_gamut(&self) -> bool {
+        let (red, green, blue) = self.to_linear_srgb();
+        [red, green, blue]
+            .iter()
+            .all(|component| (-0.0001..=1.0001).contains(component))
+    }
+}
+
+impl From<RGB> for Oklab {
+    fn from(value: RGB) -> Self {
+        let component = |value: u8| srgb_to_linear(value as f32 / 255.0);
+        Oklab::from_linear_srgb(
+            component(value.red),
+            component(value.green),
+            component(value.blue),
+        )
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(value: Oklch) -> Self {
+        let (sin, cos) = value.hue.to_radians().sin_cos();
+        Oklab {
+            lightness: value.lightness,
+            a: value.chroma * cos,
+            b: value.chroma * sin,
+        }
+    }
+}
+
+/// Converts the color to RGB, clipping each component to the sRGB gamut.
+///
+/// Clipping shifts the hue and lightness of colors far outside of the gamut, see
+/// [Oklch::clamp_chroma] for a conversion that keeps them.
+impl From<Oklab> for RGB {
+    fn from(value: Oklab) -> Self {
+        let (red, green, blue) = value.to_linear_srgb();
+        let component = |value: f32| (linear_to_srgb(value.clamp(0.0, 1.0)) * 255.0).round() as u8;
+        RGB::from((component(red), component(green), component(blue)))
+    }
+}
+
+/// Oklch Color Struct
+///
+/// Oklch is the polar form of [Oklab], with a chroma and a hue instead of the `a` and `b` axes.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Oklch {
+    /// The perceived lightness, between 0.0 for black and 1.0 for white
+    pub lightness: f32,
+
+    /// The colorfulness, from 0.0 for gray up to about 0.37 for the most saturated sRGB colors
+    pub chroma: f32,
+
+    /// The hue angle in degrees, between 0.0 and 360.0
+    pub hue: f32,
+}
+
+impl Oklch {
+    /// Reduces the chroma to the largest chroma within the sRGB gamut, keeping the lightness and
+    /// hue.
+    ///
+    /// Colors within the gamut are returned unchanged.
+    pub fn clamp_chroma(&self) -> Oklch {
+        let lightness = self.lightness.clamp(0.0, 1.0);
+        let with_chroma = |chroma| Oklch {
+            lightness,
+            chroma,
+            hue: self.hue,
+        };
+        if Oklab::from(with_chroma(self.chroma)).in_gamut() {
+            return with_chroma(self.chroma);
+        }
+
+        // Gray is always within the gamut, so search between it and the requested chroma
+        let (mut low, mut high) = (0.0, self.chroma);
+        while high - low > CHROMA_PRECISION {
+            let chroma = (low + high) / 2.0;
+            if Oklab::from(with_chroma(chroma)).in_gamut() {
+                low = chroma;
+            } else {
+                high = chroma;
+            }
+        }
+        with_chroma(low)
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(value: Oklab) -> Self {
+        Oklch {
+            lightness: value.lightness,
+            chroma: value.a.hypot(value.b),
+            hue: value.b.atan2(value.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<RGB> for Oklch {
+    fn from(value: RGB) -> Self {
+        Oklab::from(value).into()
+    }
+}
+
+/// Converts the color to RGB with [Oklch::clamp_chroma], so the lightness and hue are kept.
+impl From<Oklch> for RGB {
+    fn from(value: Oklch) -> Self {
+        Oklab::from(value.clamp_chroma()).into()
+    }
+}
+
+/// Converts a gamma encoded sRGB component between 0.0 and 1.0 to linear light.
+pub fn srgb_to_linear(component: f32) -> f32 {
+    if component <= 0.040_45 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light component between 0.0 and 1.0 to gamma encoded sRGB.
+pub fn linear_to_srgb(component: f32) -> f32 {
+    if component <= 0.003_130_8 {
+        component * 12.92
+    } else {
+        1.055 * component.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{Oklab, Oklch, RGB, linear_to_srgb, srgb_to_linear};
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.001,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn transfer_functions_round_trip() {
+        for value in [0.0, 0.01, 0.2, 0.5, 1.0] {
+            assert_close(value, linear_to_srgb(srgb_to_linear(value)));
+        }
+        assert_close(0.214, srgb_to_linear(0.5));
+    }
+
+    #[test]
+    fn oklab_matches_reference() {
+        // Reference values from https://bottosson.github.io/posts/oklab/
+        let white = Oklab::from(RGB::from((255, 255, 255)));
+        assert_close(1.0, white.lightness);
+        assert_close(0.0, white.a);
+        assert_close(0.0, white.b);
+
+        let red = Oklch::from(RGB::from((255, 0, 0)));
+        assert_close(0.628, red.lightness);
+        assert_close(0.258, red.chroma);
+        assert_close(29.234, red.hue);
+    }
+
+    #[test]
+    fn rgb_round_trips() {
+        for color in [(0, 0, 0), (255, 0, 0), (12, 200, 99), (240, 240, 240)] {
+            let color = RGB::from(color);
+            assert_eq!(color, RGB::from(Oklab::from(color)));
+            assert_eq!(color, RGB::from(Oklch::from(color)));
+        }
+    }
+
+    #[test]
+    fn clamp_chroma_stays_in_gamut() {
+        let color = Oklch {
+            lightness: 0.7,
+            chroma: 0.4,
+            hue: 250.0,
+        };
+        assert!(!Oklab::from(color).in_gamut());
+
+        let clamped = color.clamp_chroma();
+        assert!(Oklab::from(clamped).in_gamut());
+        assert!(clamped.chroma > 0.05 && clamped.chroma < 0.4);
+        assert_eq!(color.lightness, clamped.lightness);
+        assert_eq!(color.hue, clamped.hue);
+
+        let gray = Oklch {
+            chroma: 0.0,
+            ..color
+        };
+        assert_eq!(gray, gray.clamp_chroma());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::cell::{CellGap, CellStyle};
+use crate::mask::Mask;
+use crate::style::Style;
+use crate::symmetry::Symmetry;
+
+/// Plain identicon configuration struct
+///
+/// This holds the identicon settings that are plain values, so they can be stored alongside
+/// other configuration, e.g. with the `serde` feature.
+///
+/// It is applied with [`Identicon::set_config`](crate::Identicon::set_config), which validates
+/// it the same way as the individual setters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Config {
+    /// The border size.
+    pub border: u32,
+
+    /// The width and height of the grid, in viewable blocks.
+    ///
+    /// Square identicons use the same size for both, such as `(5, 5)`.
+    pub grid_dimensions: (u32, u32),
+
+    /// The width and height of the identicon portion of the image.
+    pub scale_dimensions: (u32, u32),
+
+    /// The symmetry of the identicon, where [`Symmetry::Vertical`] is a mirrored identicon.
+    pub symmetry: Symmetry,
+
+    /// The shape drawn for every active cell.
+    pub cell_style: CellStyle,
+
+    /// The gap left between neighbouring cells.
+    pub cell_gap: CellGap,
+
+    /// The shape mask of the image.
+    pub mask: Mask,
+
+    /// The render style.
+    pub style: Style,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            border: 50,
+            grid_dimensions: (5, 5),
+            scale_dimensions: (500, 500),
+            symmetry: Symmetry::Vertical,
+            cell_style: CellStyle::Square,
+            cell_gap: CellGap::Pixels(0),
+            mask: Mask::None,
+            style: Style::Grid,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::cell::{CellGap, CellStyle};
+    use crate::config::Config;
+    use crate::mask::Mask;
+    use crate::style::Style;
+    use crate::symmetry::Symmetry;
+
+    #[test]
+    fn serde_works() {
+        let config = Config {
+            border: 1,
+            grid_dimensions: (4, 5),
+            scale_dimensions: (6, 7),
+            symmetry: Symmetry::Diagonal,
+            cell_style: CellStyle::Triangle,
+            cell_gap: CellGap::Pixels(9),
+            mask: Mask::RoundedRectangle { radius: 8 },
+            style: Style::Geometric,
+        };
+        let json = serde_json::to_string(&config).expect("config should serialize");
+        assert_eq!(
+            config,
+            serde_json::from_str(&json).expect("config should deserialize")
+        );
+    }
+
+    #[test]
+    fn serde_uses_defaults() {
+        let config: Config =
+            serde_json::from_str(r#"{"border": 0}"#).expect("config should deserialize");
+        assert_eq!(
+            Config {
+                border: 0,
+                ..Config::default()
+            },
+            config
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::theme;
+use crate::{color, theme};
 
 /// Identicon errors
 #[derive(Error, Debug)]
@@ -37,14 +37,40 @@ pub enum IdenticonError {
         scale: u32,
     },
 
+    /// Indicates the pattern generated a grid with the wrong number of cells.
+    #[error("pattern generated {actual} cells, expected {expected}")]
+    PatternGridSizeError {
+        /// Expected number of cells.
+        expected: usize,
+        /// Number of cells generated by the pattern.
+        actual: usize,
+    },
+
+    /// Indicates the pattern used a palette index without a matching theme palette color.
+    #[error("pattern used palette index {index}, but the theme palette has {colors} colors")]
+    PaletteIndexError {
+        /// Palette index used by the pattern.
+        index: usize,
+        /// Number of colors in the theme palette.
+        colors: usize,
+    },
+
+    /// Indicates the hasher returned an empty hash, which identicons can not be generated from.
+    #[error("hasher returned an empty hash")]
+    EmptyHashError,
+
     /// Indicates an issue with the provided theme.
     #[error(transparent)]
     ThemeError(#[from] theme::error::ThemeError),
+
+    /// Indicates an issue with a provided color.
+    #[error(transparent)]
+    ColorError(#[from] color::error::ColorError),
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{error::IdenticonError, theme::error::ThemeError};
+    use crate::{color::error::ColorError, error::IdenticonError, theme::error::ThemeError};
 
     #[test]
     fn generate_image_error_works() {
@@ -83,6 +109,33 @@ mod tests {
         assert_eq!(expected_text, error.to_string());
     }
 
+    #[test]
+    fn pattern_grid_size_error_works() {
+        let error = IdenticonError::PatternGridSizeError {
+            expected: 25,
+            actual: 24,
+        };
+        let expected_text = "pattern generated 24 cells, expected 25";
+        assert_eq!(expected_text, error.to_string());
+    }
+
+    #[test]
+    fn palette_index_error_works() {
+        let error = IdenticonError::PaletteIndexError {
+            index: 3,
+            colors: 2,
+        };
+        let expected_text = "pattern used palette index 3, but the theme palette has 2 colors";
+        assert_eq!(expected_text, error.to_string());
+    }
+
+    #[test]
+    fn empty_hash_error_works() {
+        let error = IdenticonError::EmptyHashError;
+        let expected_text = "hasher returned an empty hash";
+        assert_eq!(expected_text, error.to_string());
+    }
+
     #[test]
     fn theme_error_works() {
         let theme_error = ThemeError::ThemeProcessingError("bad field".to_string());
@@ -96,4 +149,19 @@ mod tests {
         }
         // assert_eq!(theme_error, identicon_error);
     }
+
+    #[test]
+    fn color_error_works() {
+        let color_error = ColorError::ColorParseError {
+            input: "#12".to_string(),
+            reason: "bad length".to_string(),
+        };
+        let expected_text = color_error.to_string();
+        let identicon_error: IdenticonError = color_error.into();
+        match identicon_error {
+            IdenticonError::ColorError(_) => (),
+            _ => panic!("wrong error type"),
+        }
+        assert_eq!(expected_text, identicon_error.to_string());
+    }
 }
@@ -0,0 +1,64 @@
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+
+use crate::error::IdenticonError;
+use crate::renderer::{ExportFormat, Renderer};
+
+/// The image sizes packed into `favicon.ico`.
+pub const FAVICON_ICO_SIZES: [u32; 4] = [16, 32, 48, 64];
+
+/// The PNG files of the standard favicon set, with their image sizes.
+pub const FAVICON_PNG_FILES: [(&str, u32); 5] = [
+    ("favicon-16x16.png", 16),
+    ("favicon-32x32.png", 32),
+    ("apple-touch-icon.png", 180),
+    ("android-chrome-192x192.png", 192),
+    ("android-chrome-512x512.png", 512),
+];
+
+/// Encodes a multi-image ICO file with one image per size.
+pub fn export_ico_data(
+    renderer: &Renderer,
+    hash: &[u8],
+    image_sizes: &[u32],
+) -> Result<Vec<u8>, IdenticonError> {
+    let frames = image_sizes
+        .iter()
+        .map(|image_size| {
+            let image = renderer
+                .with_image_size(*image_size)?
+                .generate_image(hash)?
+                .to_rgba8();
+            IcoFrame::as_png(
+                image.as_raw(),
+                image.width(),
+                image.height(),
+                image::ExtendedColorType::Rgba8,
+            )
+            .map_err(|_| IdenticonError::EncodeImageError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut buffer = Vec::new();
+    IcoEncoder::new(&mut buffer)
+        .encode_images(&frames)
+        .map_err(|_| IdenticonError::EncodeImageError)?;
+    Ok(buffer)
+}
+
+/// Encodes the standard favicon set as named file buffers.
+pub fn export_favicon_set(
+    renderer: &Renderer,
+    hash: &[u8],
+) -> Result<Vec<(&'static str, Vec<u8>)>, IdenticonError> {
+    let mut files = vec![(
+        "favicon.ico",
+        export_ico_data(renderer, hash, &FAVICON_ICO_SIZES)?,
+    )];
+    for (name, image_size) in FAVICON_PNG_FILES {
+        let data = renderer
+            .with_image_size(image_size)?
+            .export_data(hash, ExportFormat::Png)?;
+        files.push((name, data));
+    }
+    Ok(files)
+}
@@ -0,0 +1,468 @@
+use std::fmt::Write;
+
+use crate::grid;
+
+/// The number of tiles along each side of a geometric identicon.
+pub(crate) const TILES: u32 = 4;
+
+/// The side tiles, filled clockwise in pairs starting at the top.
+const SIDES: [(u32, u32); 8] = [
+    (1, 0),
+    (2, 0),
+    (2, 3),
+    (1, 3),
+    (0, 1),
+    (3, 1),
+    (3, 2),
+    (0, 2),
+];
+
+/// The corner tiles, filled clockwise starting at the top left.
+const CORNERS: [(u32, u32); 4] = [(0, 0), (3, 0), (3, 3), (0, 3)];
+
+/// The center tiles, filled clockwise starting at the top left.
+const CENTER: [(u32, u32); 4] = [(1, 1), (2, 1), (2, 2), (1, 2)];
+
+/// A filled part of a shape in tile coordinates, where the tile covers 0.0 to 1.0 on both axes.
+enum Primitive {
+    /// A polygon through the points.
+    Polygon(&'static [(f32, f32)]),
+
+    /// A circle around `x`, `y`.
+    Circle { x: f32, y: f32, radius: f32 },
+}
+
+impl Primitive {
+    /// Checks if the point at `u`, `v` is within the primitive.
+    fn contains(&self, u: f32, v: f32) -> bool {
+        match self {
+            Primitive::Polygon(points) => {
+                // Count the edges crossed by a ray to the right of the point
+                let mut inside = false;
+                for (index, &(x1, y1)) in points.iter().enumerate() {
+                    let (x2, y2) = points[(index + 1) % points.len()];
+                    if (y1 > v) != (y2 > v) && u < x1 + (v - y1) * (x2 - x1) / (y2 - y1) {
+                        inside = !inside;
+                    }
+                }
+                inside
+            }
+            Primitive::Circle { x, y, radius } => {
+                (u - x).powi(2) + (v - y).powi(2) <= radius.powi(2)
+            }
+        }
+    }
+
+    /// Checks if the outline of the primitive passes through the rectangle from `left`, `top` to
+    /// `right`, `bottom`.
+    ///
+    /// Rectangles the outline does not pass through are either fully inside or fully outside.
+    fn crosses(&self, left: f32, top: f32, right: f32, bottom: f32) -> bool {
+        match self {
+            Primitive::Polygon(points) => points.iter().enumerate().any(|(index, &start)| {
+                let end = points[(index + 1) % points.len()];
+                segment_crosses(start, end, left, top, right, bottom)
+            }),
+            Primitive::Circle { x, y, radius } => {
+                // The outline passes through when the rectangle has points both closer and
+                // further away from the center than the radius
+                let nearest_x = x.clamp(left, right);
+                let nearest_y = y.clamp(top, bottom);
+                let farthest_x = (x - left).abs().max((x - right).abs());
+                let farthest_y = (y - top).abs().max((y - bottom).abs());
+                (nearest_x - x).powi(2) + (nearest_y - y).powi(2) <= radius.powi(2)
+                    && farthest_x.powi(2) + farthest_y.powi(2) >= radius.powi(2)
+            }
+        }
+    }
+}
+
+/// Checks if the line segment from `start` to `end` passes through the rectangle from `left`,
+/// `top` to `right`, `bottom`, by clipping the segment to the rectangle.
+fn segment_crosses(
+    start: (f32, f32),
+    end: (f32, f32),
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+) -> bool {
+    let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+    let bounds = [
+        (-delta_x, start.0 - left),
+        (delta_x, right - start.0),
+        (-delta_y, start.1 - top),
+        (delta_y, bottom - start.1),
+    ];
+
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+    for (direction, distance) in bounds {
+        if direction == 0.0 {
+            if distance < 0.0 {
+                return false;
+            }
+        } else if direction < 0.0 {
+            enter = enter.max(distance / direction);
+        } else {
+            exit = exit.min(distance / direction);
+        }
+    }
+    enter <= exit
+}
+
+/// A shape made of primitives, filled with the even-odd rule so primitives within a filled
+/// primitive cut holes into it.
+type Shape = &'static [Primitive];
+
+/// The full tile, used as the outline of shapes with holes.
+const FULL: Primitive = Primitive::Polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+
+/// The shapes of the center tiles.
+const CENTER_SHAPES: [Shape; 12] = [
+    &[Primitive::Polygon(&[
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (1.0, 0.16),
+        (0.58, 1.0),
+        (0.0, 1.0),
+    ])],
+    &[Primitive::Polygon(&[(0.5, 0.0), (1.0, 0.0), (1.0, 0.8)])],
+    &[Primitive::Polygon(&[
+        (1.0 / 3.0, 1.0 / 3.0),
+        (1.0, 1.0 / 3.0),
+        (1.0, 1.0),
+        (1.0 / 3.0, 1.0),
+    ])],
+    &[Primitive::Polygon(&[
+        (0.4, 0.4),
+        (0.9, 0.4),
+        (0.9, 0.9),
+        (0.4, 0.9),
+    ])],
+    &[Primitive::Circle {
+        x: 0.6,
+        y: 0.6,
+        radius: 0.25,
+    }],
+    &[
+        FULL,
+        Primitive::Polygon(&[(0.4, 0.4), (0.9, 0.4), (0.65, 0.9)]),
+    ],
+    &[Primitive::Polygon(&[
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (1.0, 0.7),
+        (0.4, 0.4),
+        (0.7, 1.0),
+        (0.0, 1.0),
+    ])],
+    &[Primitive::Polygon(&[(1.0, 0.5), (1.0, 1.0), (0.5, 1.0)])],
+    &[
+        Primitive::Polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 0.5), (0.0, 0.5)]),
+        Primitive::Polygon(&[(0.0, 0.5), (0.5, 0.5), (0.5, 1.0), (0.0, 1.0)]),
+        Primitive::Polygon(&[(0.5, 0.5), (1.0, 0.5), (0.5, 1.0)]),
+    ],
+    &[
+        FULL,
+        Primitive::Polygon(&[(0.35, 0.35), (0.86, 0.35), (0.86, 0.86), (0.35, 0.86)]),
+    ],
+    &[
+        FULL,
+        Primitive::Circle {
+            x: 0.62,
+            y: 0.62,
+            radius: 0.26,
+        },
+    ],
+    &[
+        FULL,
+        Primitive::Polygon(&[(0.5, 0.25), (0.75, 0.5), (0.5, 0.75), (0.25, 0.5)]),
+    ],
+];
+
+/// The shapes of the side and corner tiles.
+const OUTER_SHAPES: [Shape; 4] = [
+    &[Primitive::Polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)])],
+    &[Primitive::Polygon(&[(0.0, 1.0), (0.5, 0.5), (1.0, 1.0)])],
+    &[Primitive::Polygon(&[
+        (0.5, 0.0),
+        (1.0, 0.5),
+        (0.5, 1.0),
+        (0.0, 0.5),
+    ])],
+    &[Primitive::Circle {
+        x: 0.5,
+        y: 0.5,
+        radius: 1.0 / 3.0,
+    }],
+];
+
+/// The shapes and positions of a group of tiles, with the shape picked from the shapes and the
+/// rotation of the first tile.
+type Group = (&'static [Shape], &'static [(u32, u32)], u8, u8);
+
+/// A tile of a geometric identicon.
+pub(crate) struct Tile {
+    /// The column of the tile.
+    pub(crate) x: u32,
+
+    /// The row of the tile.
+    pub(crate) y: u32,
+
+    /// The palette color of the tile, starting at 0.
+    pub(crate) color: usize,
+
+    /// The shape drawn in the tile.
+    shape: Shape,
+
+    /// The number of clockwise quarter turns of the shape.
+    rotation: u8,
+}
+
+impl Tile {
+    /// Checks if the point at `u`, `v` is within the shape, where the tile covers 0.0 to 1.0 on
+    /// both axes.
+    pub(crate) fn contains(&self, u: f32, v: f32) -> bool {
+        // Turn the point back instead of turning the shape
+        let (u, v) = (0..self.rotation).fold((u, v), |(u, v), _| (v, 1.0 - u));
+        self.shape
+            .iter()
+            .filter(|primitive| primitive.contains(u, v))
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Computes the fraction of the pixel at `x`, `y` covered by the shape, in a tile of `size`
+    /// pixels.
+    ///
+    /// Shapes can be concave or have holes, so only pixels without an outline passing through
+    /// them are filled from their center, and the others are supersampled.
+    pub(crate) fn pixel_coverage(&self, x: f32, y: f32, size: f32) -> f32 {
+        // Turn the pixel back instead of turning the shape, it stays an axis aligned rectangle
+        let turn = |u: f32, v: f32| (0..self.rotation).fold((u, v), |(u, v), _| (v, 1.0 - u));
+        let (u1, v1) = turn(x / size, y / size);
+        let (u2, v2) = turn((x + 1.0) / size, (y + 1.0) / size);
+        let (left, right) = (u1.min(u2), u1.max(u2));
+        let (top, bottom) = (v1.min(v2), v1.max(v2));
+
+        if self
+            .shape
+            .iter()
+            .any(|primitive| primitive.crosses(left, top, right, bottom))
+        {
+            grid::sampled_coverage(x, y, |x, y| self.contains(x / size, y / size))
+        } else if self.contains((x + 0.5) / size, (y + 0.5) / size) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Gets the SVG path data of the shape in a tile of `size` pixels at `left`, `top`.
+    pub(crate) fn svg_path(&self, left: f32, top: f32, size: f32) -> String {
+        let point = |u: f32, v: f32| {
+            let (u, v) = (0..self.rotation).fold((u, v), |(u, v), _| (1.0 - v, u));
+            (left + u * size, top + v * size)
+        };
+
+        let mut path = String::new();
+        // Writing to a String can not fail
+        for primitive in self.shape {
+            match primitive {
+                Primitive::Polygon(points) => {
+                    for (index, (u, v)) in points.iter().enumerate() {
+                        let (x, y) = point(*u, *v);
+                        let command = if index == 0 { 'M' } else { 'L' };
+                        let _ = write!(path, "{command}{x:.2} {y:.2}");
+                    }
+                    path.push('Z');
+                }
+                Primitive::Circle { x, y, radius } => {
+                    let (x, y) = point(*x, *y);
+                    let radius = radius * size;
+                    let _ = write!(
+                        path,
+                        "M{:.2} {y:.2}a{radius:.2} {radius:.2} 0 1 0 {:.2} 0a{radius:.2} {radius:.2} 0 1 0 {:.2} 0Z",
+                        x - radius,
+                        2.0 * radius,
+                        -2.0 * radius
+                    );
+                }
+            }
+        }
+        path
+    }
+}
+
+/// Generates the 16 tiles of a geometric identicon for a palette of `colors` colors.
+///
+/// The hash is read as nibbles, like Jdenticon reads its hexadecimal hash: nibble 1 picks the
+/// center shape, nibbles 2 and 3 the side shape and rotation, nibbles 4 and 5 the corner shape and
+/// rotation, and nibbles 8 to 10 the colors of the sides, corners and center.
+/// The rotation turns by a quarter for every following tile of a group, so the tiles are
+/// rotationally symmetric.
+pub(crate) fn tiles(hash: &[u8], colors: usize) -> Vec<Tile> {
+    let nibble = |index: usize| {
+        let byte = hash
+            .get((index / 2) % hash.len().max(1))
+            .copied()
+            .unwrap_or_default();
+        if index.is_multiple_of(2) {
+            byte >> 4
+        } else {
+            byte & 0x0f
+        }
+    };
+
+    let groups: [Group; 3] = [
+        (&OUTER_SHAPES, &SIDES, nibble(2), nibble(3)),
+        (&OUTER_SHAPES, &CORNERS, nibble(4), nibble(5)),
+        (&CENTER_SHAPES, &CENTER, nibble(1), 0),
+    ];
+
+    let mut tiles = Vec::with_capacity((TILES * TILES) as usize);
+    for (group, (shapes, positions, shape, rotation)) in groups.into_iter().enumerate() {
+        let shape = shapes[shape as usize % shapes.len()];
+        let color = nibble(8 + group) as usize % colors.max(1);
+        for (index, (x, y)) in positions.iter().enumerate() {
+            tiles.push(Tile {
+                x: *x,
+                y: *y,
+                color,
+                shape,
+                rotation: (rotation + index as u8) % 4,
+            });
+        }
+    }
+    tiles
+}
+
+/// Gets the tile size and the offsets centering the tiles within a `width` x `height` area.
+///
+/// The tiles fill the shorter side, with their size rounded down to whole pixels.
+pub(crate) fn layout(width: u32, height: u32) -> (u32, u32, u32) {
+    let size = width.min(height) / TILES;
+    (
+        size,
+        (width - size * TILES) / 2,
+        (height - size * TILES) / 2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        geometric::{CENTER_SHAPES, OUTER_SHAPES, Primitive, Tile, layout, tiles},
+        grid,
+    };
+
+    #[test]
+    fn primitives_contain_points() {
+        let polygon = Primitive::Polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 0.5), (0.5, 0.5)]);
+        assert!(polygon.contains(0.9, 0.1));
+        assert!(!polygon.contains(0.1, 0.4));
+        assert!(!polygon.contains(0.5, 0.9));
+
+        let circle = Primitive::Circle {
+            x: 0.5,
+            y: 0.5,
+            radius: 0.25,
+        };
+        assert!(circle.contains(0.5, 0.3));
+        assert!(!circle.contains(0.2, 0.2));
+    }
+
+    #[test]
+    fn holes_use_even_odd_rule() {
+        let tile = Tile {
+            x: 0,
+            y: 0,
+            color: 0,
+            shape: CENTER_SHAPES[9],
+            rotation: 0,
+        };
+        assert!(tile.contains(0.1, 0.1));
+        assert!(!tile.contains(0.6, 0.6));
+    }
+
+    #[test]
+    fn pixel_coverage_matches_supersampling() {
+        for shape in CENTER_SHAPES.iter().chain(OUTER_SHAPES.iter()) {
+            for rotation in 0..4 {
+                let tile = Tile {
+                    x: 0,
+                    y: 0,
+                    color: 0,
+                    shape,
+                    rotation,
+                };
+                for size in [2, 5, 16, 23] {
+                    for pixel in 0..size * size {
+                        let (x, y) = ((pixel % size) as f32, (pixel / size) as f32);
+                        let size = size as f32;
+                        assert_eq!(
+                            grid::sampled_coverage(x, y, |x, y| tile.contains(x / size, y / size)),
+                            tile.pixel_coverage(x, y, size),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_turns_clockwise() {
+        let tile = |rotation| Tile {
+            x: 0,
+            y: 0,
+            color: 0,
+            shape: OUTER_SHAPES[0],
+            rotation,
+        };
+
+        // The triangle covers the top left corner and moves on clockwise
+        assert!(tile(0).contains(0.1, 0.1));
+        assert!(tile(1).contains(0.9, 0.1));
+        assert!(tile(2).contains(0.9, 0.9));
+        assert!(tile(3).contains(0.1, 0.9));
+        assert!(!tile(1).contains(0.1, 0.9));
+
+        assert_eq!(
+            "M1.00 0.00L1.00 1.00L0.00 0.00Z",
+            tile(1).svg_path(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn tiles_cover_layout() {
+        let hash = [0x12, 0x34, 0x56, 0x78, 0x9a];
+        let tiles = tiles(&hash, 3);
+        assert_eq!(16, tiles.len());
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(
+                    1,
+                    tiles
+                        .iter()
+                        .filter(|tile| (tile.x, tile.y) == (x, y))
+                        .count()
+                );
+            }
+        }
+
+        // Groups are rotationally symmetric and share one color
+        let rotations = |tiles: &[Tile]| tiles.iter().map(|tile| tile.rotation).collect::<Vec<_>>();
+        assert_eq!(vec![0, 1, 2, 3, 0, 1, 2, 3], rotations(&tiles[..8]));
+        assert_eq!(vec![2, 3, 0, 1], rotations(&tiles[8..12]));
+        assert_eq!(vec![0, 1, 2, 3], rotations(&tiles[12..]));
+        assert!(tiles[..8].iter().all(|tile| tile.color == 0));
+        assert!(tiles[8..].iter().all(|tile| tile.color == 1));
+    }
+
+    #[test]
+    fn layout_centers_tiles() {
+        assert_eq!((125, 0, 0), layout(500, 500));
+        assert_eq!((25, 51, 1), layout(203, 102));
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::Identicon;
+use crate::color::{RGB, hsl_to_rgb_components};
+use crate::hash::Md5Hasher;
+use crate::map_values::map_values;
+use crate::pattern::Nibbles;
+use crate::renderer::Renderer;
+use crate::symmetry::Symmetry;
+use crate::theme::Theme;
+use crate::theme::error::ThemeError;
+
+/// The grid size used by GitHub.
+const SIZE: u32 = 5;
+
+/// The pixels per cell used by GitHub.
+const CELL_SIZE: u32 = 70;
+
+/// Generates a new identicon in the style of GitHub's default avatars.
+///
+/// The input is hashed with MD5 and rendered with the [Nibbles] pattern and the [GitHubTheme] as a
+/// 5x5 grid of 70 pixel cells with a 35 pixel border, a 420x420 image.
+/// The border, scale and grid size can be changed afterwards like for any other identicon.
+///
+/// The output is not checked against images served by GitHub, only against a separate
+/// implementation of the same algorithm, see `tests/golden/README.md`.
+/// GitHub also derives an account's identicon from its numeric user ID, not its login, so passing
+/// a login does not give the avatar GitHub shows for that account.
+///
+/// Requires the `md5` feature.
+pub fn new(input_value: &str) -> Identicon {
+    let mut identicon = Identicon {
+        input: String::new(),
+        hash: Vec::new(),
+        renderer: Renderer {
+            border: CELL_SIZE / 2,
+            grid_width: SIZE,
+            grid_height: SIZE,
+            scale_width: SIZE * CELL_SIZE,
+            scale_height: SIZE * CELL_SIZE,
+            symmetry: Symmetry::Vertical,
+            theme: Arc::new(GitHubTheme),
+            hasher: Arc::new(Md5Hasher),
+            pattern: Arc::new(Nibbles),
+            ..Renderer::default()
+        },
+    };
+    identicon.set_input(input_value);
+    identicon
+}
+
+/// GitHub theme struct
+///
+/// The main color uses the last 28 bits of a 16 byte hash: 12 bits for the hue, then a byte each
+/// lowering the saturation from 65% and the lightness from 75% by up to 20%.
+/// The background is always (240, 240, 240).
+///
+/// Shorter hashes are repeated to 16 bytes.
+///
+/// Implements [Theme]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GitHubTheme;
+
+impl Theme for GitHubTheme {
+    fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        let byte = |index: usize| hash[index % hash.len()];
+        let hue_hash = (((byte(12) & 0x0f) as u16) << 8) | byte(13) as u16;
+
+        let hue = map_values(hue_hash as f32, 0.0, 4095.0, 0.0, 360.0);
+        let saturation = 65.0 - map_values(byte(14) as f32, 0.0, 255.0, 0.0, 20.0);
+        let lightness = 75.0 - map_values(byte(15) as f32, 0.0, 255.0, 0.0, 20.0);
+
+        let (red, green, blue) = hsl_to_rgb_components(hue, saturation / 100.0, lightness / 100.0);
+        Ok(RGB::from((
+            (red * 255.0).round() as u8,
+            (green * 255.0).round() as u8,
+            (blue * 255.0).round() as u8,
+        )))
+    }
+
+    fn background_color(&self, _hash: &[u8]) -> Result<RGB, ThemeError> {
+        Ok(RGB::from((240, 240, 240)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::github;
+
+    // Rendered by tests/golden/github.py, a standalone Python implementation of the algorithm,
+    // not downloaded from GitHub, see tests/golden/README.md
+    const GOLDEN_IMAGES: [(&str, &[u8]); 3] = [
+        (
+            "github",
+            include_bytes!("../tests/golden/github-github.png"),
+        ),
+        (
+            "identicon-rs",
+            include_bytes!("../tests/golden/github-identicon-rs.png"),
+        ),
+        (
+            "conways-glider",
+            include_bytes!("../tests/golden/github-conways-glider.png"),
+        ),
+    ];
+
+    #[test]
+    fn github_matches_golden_images() {
+        for (input, golden) in GOLDEN_IMAGES {
+            let expected = image::load_from_memory(golden).expect("golden image should decode");
+            let image = github::new(input)
+                .generate_image()
+                .expect("identicon should render");
+
+            assert_eq!(expected.to_rgb8(), image.to_rgb8(), "{input}");
+        }
+    }
+}
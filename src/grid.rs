@@ -1,10 +1,236 @@
+use std::ops::Range;
+
+use sha3::{
+    Shake256,
+    digest::{ExtendableOutput, Update, XofReader},
+};
+
+/// The number of samples along each axis used to anti-alias edge pixels.
+const COVERAGE_SAMPLES: u32 = 8;
+
 /// Identicon grid generation.
 ///
 /// The hash determines the grid. Each even byte is an active square.
-pub fn generate_full_grid<T: Into<u32>>(image_size: T, hash: &[u8]) -> Vec<bool> {
+pub fn generate_full_grid(width: u32, height: u32, hash: &[u8]) -> Vec<bool> {
     // Compute the hash value
-    let square_count = (image_size.into() as usize).pow(2);
+    let square_count = (width * height) as usize;
     (0..square_count)
         .map(|location| hash[location % hash.len()].is_multiple_of(2))
         .collect()
 }
+
+/// Identicon grid generation from individual bits.
+///
+/// Each set bit is an active square, starting with the most significant bit of the first byte.
+/// When the hash runs out of bits, it is extended with the SHAKE256 output of the hash, so no
+/// bits are ever reused.
+pub fn generate_bit_grid(width: u32, height: u32, hash: &[u8]) -> Vec<bool> {
+    let square_count = (width * height) as usize;
+    let bytes = extend_hash(hash, square_count.div_ceil(8));
+
+    (0..square_count)
+        .map(|location| bytes[location / 8] & (0x80 >> (location % 8)) != 0)
+        .collect()
+}
+
+/// Identicon grid generation from nibbles, as done by GitHub.
+///
+/// Each even nibble is an active square, starting with the high nibble of the first byte.
+/// The nibbles fill the columns from the middle column to the left edge, each from top to bottom,
+/// and are mirrored onto the right half. Squares are inactive once the hash runs out of nibbles.
+pub fn generate_nibble_grid(width: u32, height: u32, hash: &[u8]) -> Vec<bool> {
+    let mut nibbles = hash
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .map(|nibble| nibble.is_multiple_of(2));
+
+    let mut grid = vec![false; (width * height) as usize];
+    for x in (0..width.div_ceil(2)).rev() {
+        for y in 0..height {
+            let active = nibbles.next().unwrap_or(false);
+            grid[(y * width + x) as usize] = active;
+            grid[(y * width + width - 1 - x) as usize] = active;
+        }
+    }
+    grid
+}
+
+/// Extends the hash to at least `length` bytes using SHAKE256.
+///
+/// The original hash bytes are kept as the prefix of the output.
+pub fn extend_hash(hash: &[u8], length: usize) -> Vec<u8> {
+    let mut bytes = hash.to_vec();
+    if bytes.len() < length {
+        let mut extension = vec![0; length - bytes.len()];
+        let mut shake = Shake256::default();
+        shake.update(hash);
+        shake.finalize_xof().read(&mut extension);
+        bytes.extend(extension);
+    }
+    bytes
+}
+
+/// Computes the pixel boundaries of `cells` cells spread over `length` pixels.
+///
+/// Cell `i` covers the pixels from `boundaries[i]` up to, but not including, `boundaries[i + 1]`.
+/// Pixel `p` belongs to cell `floor((p + 0.5) * cells / length)`, the pixel centres sampled by a
+/// nearest neighbour resize, so images match the ones scaled up from a grid of one pixel cells.
+/// When `length` is not a multiple of `cells`, the leftover pixels are spread evenly, so cells
+/// differ in size by at most one pixel.
+pub fn cell_boundaries(cells: u32, length: u32) -> Vec<u32> {
+    let (cells, length) = (cells.max(1) as u64, length as u64);
+    // The first pixel with its centre in cell `i`, i.e. `ceil(i * length / cells - 0.5)`
+    (0..=cells)
+        .map(|cell| ((2 * cell * length + cells - 1) / (2 * cells)) as u32)
+        .collect()
+}
+
+/// Computes the pixel spans of `cells` cells spread over `length` pixels, with `gap` pixels
+/// between neighbouring cells.
+///
+/// The cells are spread over `length + gap` pixels by [`cell_boundaries`], with every cell
+/// followed by a gap, so the last gap falls outside of the length.
+pub fn cell_spans(cells: u32, length: u32, gap: u32) -> Vec<Range<u32>> {
+    cell_boundaries(cells, length + gap)
+        .windows(2)
+        .map(|bounds| bounds[0]..bounds[1].saturating_sub(gap).max(bounds[0]))
+        .collect()
+}
+
+/// Maps every pixel of `length` pixels to the cell containing it, or `None` for gap pixels.
+pub fn pixel_cells(spans: &[Range<u32>], length: u32) -> Vec<Option<u32>> {
+    let mut cells = vec![None; length as usize];
+    for (cell, span) in spans.iter().enumerate() {
+        for pixel in span.clone() {
+            cells[pixel as usize] = Some(cell as u32);
+        }
+    }
+    cells
+}
+
+/// Computes the fraction of the pixel at `x`, `y` covered by a convex shape.
+///
+/// A pixel with all of its corners inside the shape is fully covered. Other pixels are
+/// supersampled with [sampled_coverage], which anti-aliases the edges.
+pub fn pixel_coverage(x: f32, y: f32, contains: impl Fn(f32, f32) -> bool) -> f32 {
+    let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)];
+    if corners
+        .iter()
+        .all(|(corner_x, corner_y)| contains(x + corner_x, y + corner_y))
+    {
+        return 1.0;
+    }
+
+    sampled_coverage(x, y, contains)
+}
+
+/// Computes the fraction of the pixel at `x`, `y` covered by any shape, including concave shapes
+/// and shapes with holes.
+///
+/// The pixel is supersampled with `COVERAGE_SAMPLES` x `COVERAGE_SAMPLES` samples.
+pub fn sampled_coverage(x: f32, y: f32, contains: impl Fn(f32, f32) -> bool) -> f32 {
+    let covered = (0..COVERAGE_SAMPLES.pow(2))
+        .filter(|sample| {
+            let sample_x = x + ((sample % COVERAGE_SAMPLES) as f32 + 0.5) / COVERAGE_SAMPLES as f32;
+            let sample_y = y + ((sample / COVERAGE_SAMPLES) as f32 + 0.5) / COVERAGE_SAMPLES as f32;
+            contains(sample_x, sample_y)
+        })
+        .count();
+    covered as f32 / COVERAGE_SAMPLES.pow(2) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        grid::{
+            cell_boundaries, cell_spans, extend_hash, generate_bit_grid, generate_nibble_grid,
+            pixel_cells, pixel_coverage, sampled_coverage,
+        },
+        hash::hash_value,
+    };
+
+    #[test]
+    fn extend_hash_keeps_prefix() {
+        let hash = hash_value("test");
+        let extended = extend_hash(&hash, 100);
+
+        assert_eq!(100, extended.len());
+        assert_eq!(hash, extended[..hash.len()]);
+        assert_eq!(hash, extend_hash(&hash, 10));
+    }
+
+    #[test]
+    fn bit_grid_uses_hash_bits() {
+        let hash = [0b1010_0000, 0b0000_0001];
+        let grid = generate_bit_grid(4, 4, &hash);
+
+        let expected = vec![
+            true, false, true, false, false, false, false, false, false, false, false, false,
+            false, false, false, true,
+        ];
+        assert_eq!(expected, grid);
+    }
+
+    #[test]
+    fn bit_grid_does_not_repeat() {
+        let hash = hash_value("test");
+        let grid = generate_bit_grid(16, 16, &hash);
+
+        // The 256 bits of the hash are followed by new bits, not a repeat of the hash
+        assert_eq!(256, grid.len());
+        let grid = generate_bit_grid(32, 32, &hash);
+        assert_ne!(grid[..256], grid[256..512]);
+    }
+
+    #[test]
+    fn nibble_grid_fills_columns_from_the_middle() {
+        let grid = generate_nibble_grid(3, 2, &[0x01, 0x10]);
+
+        let expected = vec![false, true, false, true, false, true];
+        assert_eq!(expected, grid);
+    }
+
+    #[test]
+    fn cell_boundaries_spread_leftover_pixels() {
+        assert_eq!(vec![0, 100, 200, 300, 400, 500], cell_boundaries(5, 500));
+        assert_eq!(vec![0, 26, 51, 77, 102, 128], cell_boundaries(5, 128));
+    }
+
+    #[test]
+    fn cell_spans_leave_gaps() {
+        assert_eq!(vec![0..100, 100..200, 200..300], cell_spans(3, 300, 0));
+        assert_eq!(vec![0..93, 103..197, 207..300], cell_spans(3, 300, 10));
+    }
+
+    #[test]
+    fn pixel_cells_covers_length() {
+        let cells = pixel_cells(&cell_spans(5, 128, 0), 128);
+        assert_eq!(128, cells.len());
+        assert_eq!(Some(0), cells[25]);
+        assert_eq!(Some(1), cells[26]);
+        assert_eq!(Some(4), cells[127]);
+
+        let cells = pixel_cells(&cell_spans(3, 300, 10), 300);
+        assert_eq!(Some(0), cells[92]);
+        assert_eq!(None, cells[93]);
+        assert_eq!(None, cells[102]);
+        assert_eq!(Some(1), cells[103]);
+        assert_eq!(Some(2), cells[299]);
+    }
+
+    #[test]
+    fn pixel_coverage_works() {
+        assert_eq!(1.0, pixel_coverage(0.0, 0.0, |_, _| true));
+        assert_eq!(0.0, pixel_coverage(0.0, 0.0, |_, _| false));
+        assert_eq!(0.5, pixel_coverage(0.0, 0.0, |x, _| x < 0.5));
+    }
+
+    #[test]
+    fn sampled_coverage_finds_holes() {
+        // A hole in the middle of the pixel, missed by the corners
+        let contains = |x: f32, y: f32| !((0.25..0.75).contains(&x) && (0.25..0.75).contains(&y));
+        assert_eq!(1.0, pixel_coverage(0.0, 0.0, contains));
+        assert_eq!(0.75, sampled_coverage(0.0, 0.0, contains));
+        assert_eq!(1.0, sampled_coverage(0.0, 0.0, |_, _| true));
+    }
+}
@@ -1,8 +1,155 @@
+use std::sync::Arc;
+
 use sha3::{Digest, Sha3_256};
 
-pub fn hash_value(input_value: &str) -> Vec<u8> {
-    let input_trimmed = input_value.trim();
-    Sha3_256::digest(input_trimmed.as_bytes())
-        .as_slice()
-        .to_vec()
+use crate::error::IdenticonError;
+
+/// Trait defining how an input value is turned into the bytes an identicon is generated from
+///
+/// The input is trimmed by [`Identicon`](crate::Identicon) before it is passed to the hasher.
+pub trait Hasher {
+    /// This should return the hash of the input bytes
+    ///
+    /// The hash must not be empty, otherwise generating the identicon fails with
+    /// [`IdenticonError::EmptyHashError`].
+    fn hash(&self, input: &[u8]) -> Vec<u8>;
+}
+
+/// Checks that the hash is not empty, as every identicon is generated from at least one byte.
+pub(crate) fn check_hash(hash: &[u8]) -> Result<&[u8], IdenticonError> {
+    if hash.is_empty() {
+        Err(IdenticonError::EmptyHashError)
+    } else {
+        Ok(hash)
+    }
+}
+
+/// SHA3-256 hasher
+///
+/// This is the default hasher.
+///
+/// Implements [Hasher]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha3_256Hasher;
+
+impl Hasher for Sha3_256Hasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        Sha3_256::digest(input).as_slice().to_vec()
+    }
+}
+
+/// MD5 hasher
+///
+/// This is useful for reproducing identicons from systems that use MD5.
+///
+/// Requires the `md5` feature.
+///
+/// Implements [Hasher]
+#[cfg(feature = "md5")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Md5Hasher;
+
+#[cfg(feature = "md5")]
+impl Hasher for Md5Hasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        md5::Md5::digest(input).as_slice().to_vec()
+    }
+}
+
+/// SHA-1 hasher
+///
+/// This is useful for reproducing identicons from systems that use SHA-1.
+///
+/// Requires the `sha1` feature.
+///
+/// Implements [Hasher]
+#[cfg(feature = "sha1")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha1Hasher;
+
+#[cfg(feature = "sha1")]
+impl Hasher for Sha1Hasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        sha1::Sha1::digest(input).as_slice().to_vec()
+    }
+}
+
+/// BLAKE3 hasher
+///
+/// This is a fast hasher for bulk generation.
+///
+/// Requires the `blake3` feature.
+///
+/// Implements [Hasher]
+#[cfg(feature = "blake3")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Blake3Hasher;
+
+#[cfg(feature = "blake3")]
+impl Hasher for Blake3Hasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        blake3::hash(input).as_bytes().to_vec()
+    }
+}
+
+/// The default hasher
+///
+/// This is SHA3-256, the original hash before hashers were customizable.
+pub fn default_hasher() -> Arc<dyn Hasher + Send + Sync> {
+    Arc::new(Sha3_256Hasher)
+}
+
+/// Hashes a trimmed input value with the default hasher.
+pub(crate) fn hash_value(input_value: &str) -> Vec<u8> {
+    Sha3_256Hasher.hash(input_value.trim().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hasher, Sha3_256Hasher, hash_value};
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    #[test]
+    fn sha3_256_works() {
+        assert_eq!(
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            to_hex(&Sha3_256Hasher.hash(b""))
+        );
+    }
+
+    #[test]
+    fn hash_value_trims_input() {
+        assert_eq!(hash_value("test"), hash_value("  test  "));
+        assert_eq!(hash_value("test"), Sha3_256Hasher.hash(b"test"));
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn md5_works() {
+        assert_eq!(
+            "d41d8cd98f00b204e9800998ecf8427e",
+            to_hex(&super::Md5Hasher.hash(b""))
+        );
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn sha1_works() {
+        assert_eq!(
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            to_hex(&super::Sha1Hasher.hash(b"abc"))
+        );
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_works() {
+        assert_eq!(
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            to_hex(&super::Blake3Hasher.hash(b""))
+        );
+    }
 }
@@ -7,11 +7,16 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
+use crate::cell::{CellGap, CellStyle};
+use crate::config::Config;
 use crate::error::IdenticonError;
-use image::codecs::jpeg::JpegEncoder;
-use image::codecs::png::PngEncoder;
-use image::imageops::FilterType;
-use image::{DynamicImage, GenericImage, ImageBuffer, ImageEncoder};
+use crate::hash::Hasher;
+use crate::mask::Mask;
+use crate::pattern::Pattern;
+use crate::renderer::{ExportFormat, Renderer};
+use crate::style::Style;
+use crate::symmetry::Symmetry;
+use image::DynamicImage;
 use theme::Theme;
 
 /// Identicon errors
@@ -20,24 +25,55 @@ pub mod error;
 /// Theme Trait and Structs
 pub mod theme;
 
+/// Ethereum Blockies Compatibility
+pub mod blockies;
+
+/// GitHub Style Identicons
+#[cfg(feature = "md5")]
+pub mod github;
+
+/// Cell Shape Styles
+pub mod cell;
+
 /// Color Structs and Implementations
 pub mod color;
 
+/// Plain Identicon Configuration
+pub mod config;
+
+/// Hasher Trait and Structs
+pub mod hash;
+
+/// Pattern Trait and Structs
+pub mod pattern;
+
+/// Avatar Shape Masks
+pub mod mask;
+
+/// Reusable Renderer and Export Formats
+pub mod renderer;
+
+/// Overall Render Styles
+pub mod style;
+
+/// Grid Symmetry
+pub mod symmetry;
+
+#[cfg(feature = "ico")]
+mod favicon;
+mod geometric;
 mod grid;
-mod hash;
 mod map_values;
+mod svg;
 
 /// Generic Identicon struct.
 ///
 /// This is the base struct to be used.
 #[derive(Clone)]
 pub struct Identicon {
+    input: String,
     hash: Vec<u8>,
-    border: u32,
-    size: u32,
-    scale: u32,
-    mirrored: bool,
-    theme: Arc<dyn Theme + Send + Sync>,
+    renderer: Renderer,
 }
 
 /// Generates a new identicon.
@@ -57,7 +93,7 @@ impl Identicon {
     /// - size: 5
     /// - scale: 500
     /// - background_color: (240, 240, 240)
-    /// - mirrored: true
+    /// - symmetry: vertical
     pub fn new(input_value: &str) -> Identicon {
         let mut identicon = Identicon::default();
         identicon.set_input(input_value);
@@ -65,174 +101,426 @@ impl Identicon {
     }
 
     /// Sets the identicon input value, regenerating the hash.
+    ///
+    /// The input value is trimmed before it is hashed.
     pub fn set_input(&mut self, input_value: &str) -> &mut Self {
-        self.hash = hash::hash_value(input_value);
+        self.input = input_value.trim().to_string();
+        self.hash = self.renderer.hasher.hash(self.input.as_bytes());
         self
     }
 
     /// Gets the identicon border size.
     pub fn border(&self) -> u32 {
-        self.border
+        self.renderer.border
     }
 
     /// Sets the identicon border size.
     ///
     /// Default is 5
     pub fn set_border(&mut self, border: u32) -> &mut Self {
-        self.border = border;
+        self.renderer.border = border;
         self
     }
 
     /// Gets the identicon size.
     ///
     /// The size represents the number of viewable blocks of the identicon.
+    /// For rectangular grids this is the width, see [`Identicon::grid_dimensions`].
     pub fn size(&self) -> u32 {
-        self.size
+        self.renderer.grid_width
     }
 
-    /// Sets the number of viewable blocks of the identicon.
+    /// Sets the number of viewable blocks of the identicon, making the grid square.
     ///
-    /// This must be <= the scale.
+    /// This must be <= the scale along both axes.
     ///
     /// Default is 5, representing an identicon with a grid of 5x5.
     pub fn set_size(&mut self, size: u32) -> Result<&mut Self, IdenticonError> {
-        if size <= self.scale {
-            self.size = size;
-            Ok(self)
-        } else {
-            Err(IdenticonError::SizeTooLargeError {
-                size,
-                scale: self.scale,
-            })
+        self.set_grid_dimensions(size, size)
+    }
+
+    /// Gets the width and height of the identicon grid, in blocks.
+    pub fn grid_dimensions(&self) -> (u32, u32) {
+        (self.renderer.grid_width, self.renderer.grid_height)
+    }
+
+    /// Sets the number of viewable blocks along each axis of the identicon.
+    ///
+    /// The width must be <= the scale width, and the height must be <= the scale height.
+    ///
+    /// Default is 5x5.
+    pub fn set_grid_dimensions(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<&mut Self, IdenticonError> {
+        for (size, scale) in [
+            (width, self.renderer.scale_width),
+            (height, self.renderer.scale_height),
+        ] {
+            if size > scale {
+                return Err(IdenticonError::SizeTooLargeError { size, scale });
+            }
         }
+
+        self.renderer.grid_width = width;
+        self.renderer.grid_height = height;
+        Ok(self)
     }
 
     /// Gets the identicon scale.
     ///
     /// The scale represents the height and width of the identicon portion of any generated image.
+    /// For rectangular images this is the width, see [`Identicon::scale_dimensions`].
     ///
     /// The full image size is: `scale + ( 2 * border )`
     pub fn scale(&self) -> u32 {
-        self.scale
+        self.renderer.scale_width
     }
 
     /// Sets the scale of the image.
     ///
     /// The full image size is: `scale + ( 2 * border )`
     ///
-    /// This must be >= the size.
+    /// When the scale is not a multiple of the size, the leftover pixels are spread evenly
+    /// between the cells: pixel `p` belongs to cell `floor((p + 0.5) * size / scale)`, so cells
+    /// differ in width by at most one pixel.
+    ///
+    /// This must be >= the size along both axes.
     pub fn set_scale(&mut self, scale: u32) -> Result<&mut Self, IdenticonError> {
-        if scale >= self.size {
-            self.scale = scale;
-            Ok(self)
-        } else {
-            Err(IdenticonError::ScaleTooSmallError {
-                scale,
-                size: self.size,
-            })
+        self.set_scale_dimensions(scale, scale)
+    }
+
+    /// Gets the width and height of the identicon portion of any generated image.
+    pub fn scale_dimensions(&self) -> (u32, u32) {
+        (self.renderer.scale_width, self.renderer.scale_height)
+    }
+
+    /// Sets the width and height of the identicon portion of the image, e.g. for banners.
+    ///
+    /// The full image size is: `(width + ( 2 * border ), height + ( 2 * border ))`
+    ///
+    /// The width must be >= the grid width, and the height must be >= the grid height.
+    ///
+    /// Default is 500x500.
+    pub fn set_scale_dimensions(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<&mut Self, IdenticonError> {
+        for (scale, size) in [
+            (width, self.renderer.grid_width),
+            (height, self.renderer.grid_height),
+        ] {
+            if scale < size {
+                return Err(IdenticonError::ScaleTooSmallError { scale, size });
+            }
         }
+
+        self.renderer.scale_width = width;
+        self.renderer.scale_height = height;
+        Ok(self)
     }
 
-    /// Gets if the identicon is mirrored.
+    /// Gets if the identicon is mirrored along the y axis.
+    ///
+    /// This is true for [`Symmetry::Vertical`], [`Symmetry::VerticalCentred`] and
+    /// [`Symmetry::Both`].
     pub fn mirrored(&self) -> bool {
-        self.mirrored
+        matches!(
+            self.renderer.symmetry,
+            Symmetry::Vertical | Symmetry::VerticalCentred | Symmetry::Both
+        )
     }
 
     /// Sets whether the identicon is mirrored along the y axis.
     ///
-    /// This is a boolean.
+    /// This is a boolean, `true` sets [`Symmetry::Vertical`] and `false` sets [`Symmetry::None`].
     pub fn set_mirrored(&mut self, mirrored: bool) -> &mut Self {
-        self.mirrored = mirrored;
+        self.renderer.symmetry = if mirrored {
+            Symmetry::Vertical
+        } else {
+            Symmetry::None
+        };
+        self
+    }
+
+    /// Gets the identicon symmetry.
+    pub fn symmetry(&self) -> Symmetry {
+        self.renderer.symmetry
+    }
+
+    /// Sets the identicon symmetry.
+    ///
+    /// Default is [`Symmetry::Vertical`]
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) -> &mut Self {
+        self.renderer.symmetry = symmetry;
+        self
+    }
+
+    /// Gets the cell style.
+    pub fn cell_style(&self) -> CellStyle {
+        self.renderer.cell_style
+    }
+
+    /// Sets the shape drawn for every active cell, such as circles for a dotted style.
+    ///
+    /// Default is [`CellStyle::Square`]
+    pub fn set_cell_style(&mut self, cell_style: CellStyle) -> &mut Self {
+        self.renderer.cell_style = cell_style;
+        self
+    }
+
+    /// Gets the gap between cells.
+    pub fn cell_gap(&self) -> CellGap {
+        self.renderer.cell_gap
+    }
+
+    /// Sets the gap left between neighbouring cells, in pixels or as a fraction of the cell size.
+    ///
+    /// The gap is filled with the background color. It is only placed between cells, so the full
+    /// image size stays `scale + ( 2 * border )`.
+    ///
+    /// Default is `CellGap::Pixels(0)`
+    pub fn set_cell_gap(&mut self, cell_gap: CellGap) -> &mut Self {
+        self.renderer.cell_gap = cell_gap;
+        self
+    }
+
+    /// Gets the background alpha override.
+    pub fn background_alpha(&self) -> Option<u8> {
+        self.renderer.background_alpha
+    }
+
+    /// Sets the alpha of the background and border, overriding the alpha provided by the theme.
+    ///
+    /// `Some(0)` renders a fully transparent background, `None` uses the theme's alpha.
+    ///
+    /// Any non-opaque color causes [`Identicon::generate_image`] to return an RGBA image.
+    ///
+    /// Default is `None`
+    pub fn set_background_alpha(&mut self, background_alpha: Option<u8>) -> &mut Self {
+        self.renderer.background_alpha = background_alpha;
+        self
+    }
+
+    /// Gets the render style.
+    pub fn style(&self) -> Style {
+        self.renderer.style
+    }
+
+    /// Sets the render style, such as geometric shapes instead of a grid.
+    ///
+    /// Default is [`Style::Grid`]
+    pub fn set_style(&mut self, style: Style) -> &mut Self {
+        self.renderer.style = style;
+        self
+    }
+
+    /// Gets the shape mask.
+    pub fn mask(&self) -> Mask {
+        self.renderer.mask
+    }
+
+    /// Sets the shape mask, such as a circle for round avatars.
+    ///
+    /// Pixels outside of the shape, including the border, are transparent and the edges are
+    /// anti-aliased, so any mask causes [`Identicon::generate_image`] to return an RGBA image.
+    ///
+    /// Default is [`Mask::None`]
+    pub fn set_mask(&mut self, mask: Mask) -> &mut Self {
+        self.renderer.mask = mask;
         self
     }
 
     /// Gets the current theme.
     pub fn theme(&self) -> Arc<dyn Theme> {
-        self.theme.clone()
+        self.renderer.theme.clone()
     }
 
     /// Sets the current identicon theme.
     pub fn set_theme(&mut self, theme: Arc<dyn Theme + Send + Sync>) -> &mut Self {
-        self.theme = theme;
+        self.renderer.theme = theme;
         self
     }
 
-    /// Generates the DynamicImage representing the Identicon.
-    pub fn generate_image(&self) -> Result<DynamicImage, IdenticonError> {
-        // Create a new grid
-        let grid = grid::generate_full_grid(self.size, &self.hash);
-
-        // Create pixel objects
-        let color_active = self.theme.main_color(&self.hash)?;
-        let color_background = self.theme.background_color(&self.hash)?;
-        let pixel_active = image::Rgb([color_active.red, color_active.green, color_active.blue]);
-        let pixel_background = image::Rgb([
-            color_background.red,
-            color_background.green,
-            color_background.blue,
-        ]);
-
-        // Create image buffer from grid
-        let image_buffer = ImageBuffer::from_fn(self.size, self.size, |x, y| {
-            let x_location = if self.mirrored && x > self.size / 2 {
-                self.size - x - 1
-            } else {
-                x
-            };
-
-            // Get location within the generated grid
-            let grid_location = (x_location + y * self.size) % self.size.pow(2);
-
-            // Set the pixel color based on the value within the grid at the given position
-            if grid[grid_location as usize] {
-                pixel_active
-            } else {
-                pixel_background
-            }
-        });
+    /// Gets the current hasher.
+    pub fn hasher(&self) -> Arc<dyn Hasher> {
+        self.renderer.hasher.clone()
+    }
 
-        let scaled_image_buffer = DynamicImage::ImageRgb8(image_buffer)
-            .resize(self.scale, self.scale, FilterType::Nearest)
-            .to_rgb8();
+    /// Sets the current identicon hasher, regenerating the hash.
+    ///
+    /// Default is [`hash::default_hasher`], which uses SHA3-256.
+    pub fn set_hasher(&mut self, hasher: Arc<dyn Hasher + Send + Sync>) -> &mut Self {
+        self.renderer.hasher = hasher;
+        self.hash = self.renderer.hasher.hash(self.input.as_bytes());
+        self
+    }
 
-        let final_size = self.scale + (2 * self.border);
-        let mut bordered_image_buffer =
-            ImageBuffer::from_fn(final_size, final_size, |_, _| pixel_background);
+    /// Gets the current pattern.
+    pub fn pattern(&self) -> Arc<dyn Pattern> {
+        self.renderer.pattern.clone()
+    }
 
-        match bordered_image_buffer.copy_from(&scaled_image_buffer, self.border, self.border) {
-            Ok(_) => Ok(DynamicImage::ImageRgb8(bordered_image_buffer)),
-            Err(_) => Err(error::IdenticonError::GenerateImageError),
+    /// Sets the current identicon pattern.
+    ///
+    /// Default is [`pattern::default_pattern`].
+    pub fn set_pattern(&mut self, pattern: Arc<dyn Pattern + Send + Sync>) -> &mut Self {
+        self.renderer.pattern = pattern;
+        self
+    }
+
+    /// Gets the plain identicon configuration.
+    pub fn config(&self) -> Config {
+        Config {
+            border: self.renderer.border,
+            grid_dimensions: self.grid_dimensions(),
+            scale_dimensions: self.scale_dimensions(),
+            symmetry: self.renderer.symmetry,
+            cell_style: self.renderer.cell_style,
+            cell_gap: self.renderer.cell_gap,
+            mask: self.renderer.mask,
+            style: self.renderer.style,
         }
     }
 
+    /// Sets the plain identicon configuration.
+    ///
+    /// The size must be <= the scale along both axes, otherwise nothing is changed.
+    pub fn set_config(&mut self, config: &Config) -> Result<&mut Self, IdenticonError> {
+        let (grid_width, grid_height) = config.grid_dimensions;
+        let (scale_width, scale_height) = config.scale_dimensions;
+        for (size, scale) in [(grid_width, scale_width), (grid_height, scale_height)] {
+            if size > scale {
+                return Err(IdenticonError::SizeTooLargeError { size, scale });
+            }
+        }
+
+        self.renderer.border = config.border;
+        self.renderer.grid_width = grid_width;
+        self.renderer.grid_height = grid_height;
+        self.renderer.scale_width = scale_width;
+        self.renderer.scale_height = scale_height;
+        self.renderer.symmetry = config.symmetry;
+        self.renderer.cell_style = config.cell_style;
+        self.renderer.cell_gap = config.cell_gap;
+        self.renderer.mask = config.mask;
+        self.renderer.style = config.style;
+        Ok(self)
+    }
+
+    /// Gets the hash the identicon is generated from, rejecting empty hashes from custom hashers.
+    fn checked_hash(&self) -> Result<&[u8], IdenticonError> {
+        hash::check_hash(&self.hash)
+    }
+
+    /// Gets a reusable renderer with the current identicon settings.
+    ///
+    /// The renderer does not hold the input value, so it can be stored in shared application
+    /// state and used to render identicons for any input.
+    pub fn renderer(&self) -> Renderer {
+        self.renderer.clone()
+    }
+
+    /// Generates the DynamicImage representing the Identicon.
+    ///
+    /// The image is RGB, unless the theme or background alpha make any color non-opaque, in which
+    /// case it is RGBA.
+    pub fn generate_image(&self) -> Result<DynamicImage, IdenticonError> {
+        self.renderer.generate_image(self.checked_hash()?)
+    }
+
+    /// Generates the DynamicImage representing the Identicon at `image_size` x `image_size` pixels.
+    ///
+    /// The border and scale are adjusted proportionally, so the result looks the same as
+    /// [`Identicon::generate_image`] at a different resolution.
+    /// Rectangular identicons are `image_size` pixels along their longer side.
+    pub fn generate_image_with_size(
+        &self,
+        image_size: u32,
+    ) -> Result<DynamicImage, IdenticonError> {
+        self.renderer
+            .with_image_size(image_size)?
+            .generate_image(self.checked_hash()?)
+    }
+
+    /// Generates the SVG document representing the Identicon.
+    ///
+    /// Each active cell is emitted as its own `<rect>`, so the output scales to any display size.
+    pub fn generate_svg(&self) -> Result<String, IdenticonError> {
+        self.renderer.generate_svg(self.checked_hash()?)
+    }
+
     /// Saves the generated image to the given filename.
     ///
-    /// The file formats `.png`, `.jpg`, `.jpeg`, `.bmp`, and `.ico` work.
+    /// The format is chosen by the file extension, see [`ExportFormat::from_extension`].
+    /// The file formats `.png`, `.jpg`, `.jpeg`, and `.svg` always work.
+    /// The file formats `.webp`, `.gif`, `.bmp`, `.ico`, `.avif`, and `.qoi` work when their
+    /// cargo features are enabled.
     pub fn save_image(&self, output_filename: &str) -> Result<(), error::IdenticonError> {
-        let image = self.generate_image()?;
-        image
-            .save(output_filename)
-            .map_err(|_| error::IdenticonError::SaveImageError)
+        let format = std::path::Path::new(output_filename)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(ExportFormat::from_extension)
+            .ok_or(error::IdenticonError::SaveImageError)?;
+
+        let data = self.export_data(format)?;
+        std::fs::write(output_filename, data).map_err(|_| error::IdenticonError::SaveImageError)
+    }
+
+    /// Export a file buffer in the given format as a `Vec<u8>`.
+    ///
+    /// This is for creating a file for a buffer or network response without creating a file on the
+    /// filesystem.
+    pub fn export_data(&self, format: ExportFormat) -> Result<Vec<u8>, error::IdenticonError> {
+        self.renderer.export_data(self.checked_hash()?, format)
     }
 
     /// Export a PNG file buffer as a `Vec<u8>`.
     ///
     /// This is for creating a file for a buffer or network response without creating a file on the
     /// filesystem.
+    ///
+    /// The alpha channel is preserved for transparent identicons.
     pub fn export_png_data(&self) -> Result<Vec<u8>, error::IdenticonError> {
-        let image = self.generate_image()?;
-        let image_size = image.to_rgb8().width();
-        let mut buffer = Vec::new();
+        self.export_data(ExportFormat::Png)
+    }
 
-        PngEncoder::new(&mut buffer)
-            .write_image(
-                image.to_rgb8().into_raw().as_slice(),
-                image_size,
-                image_size,
-                image::ExtendedColorType::Rgb8,
-            )
-            .map_err(|_| error::IdenticonError::EncodeImageError)?;
-        Ok(buffer)
+    /// Export an SVG file buffer as a `Vec<u8>`.
+    ///
+    /// This is for creating a file for a buffer or network response without creating a file on the
+    /// filesystem.
+    pub fn export_svg_data(&self) -> Result<Vec<u8>, error::IdenticonError> {
+        self.export_data(ExportFormat::Svg)
+    }
+
+    /// Export a multi-image ICO file buffer as a `Vec<u8>`.
+    ///
+    /// The identicon is rendered once for every image size, as with
+    /// [`Identicon::generate_image_with_size`].
+    /// ICO images are limited to at most 256x256 pixels.
+    ///
+    /// Requires the `ico` feature.
+    #[cfg(feature = "ico")]
+    pub fn export_ico_data(&self, image_sizes: &[u32]) -> Result<Vec<u8>, error::IdenticonError> {
+        favicon::export_ico_data(&self.renderer, self.checked_hash()?, image_sizes)
+    }
+
+    /// Export the standard favicon set as named file buffers.
+    ///
+    /// The set contains:
+    /// - `favicon.ico`, with 16, 32, 48, and 64 pixel images
+    /// - `favicon-16x16.png` and `favicon-32x32.png`
+    /// - `apple-touch-icon.png`, at 180 pixels
+    /// - `android-chrome-192x192.png` and `android-chrome-512x512.png`
+    ///
+    /// Requires the `ico` feature.
+    #[cfg(feature = "ico")]
+    pub fn export_favicon_set(
+        &self,
+    ) -> Result<Vec<(&'static str, Vec<u8>)>, error::IdenticonError> {
+        favicon::export_favicon_set(&self.renderer, self.checked_hash()?)
     }
 
     /// Export a JPEG file buffer as a `Vec<u8>`.
@@ -240,32 +528,16 @@ impl Identicon {
     /// This is for creating a file for a buffer or network response without creating a file on the
     /// filesystem.
     pub fn export_jpeg_data(&self) -> Result<Vec<u8>, error::IdenticonError> {
-        let image = self.generate_image()?;
-        let image_size = image.to_rgb8().width();
-        let mut buffer = Vec::new();
-
-        JpegEncoder::new(&mut buffer)
-            .write_image(
-                image.to_rgb8().into_raw().as_slice(),
-                image_size,
-                image_size,
-                image::ExtendedColorType::Rgb8,
-            )
-            .map_err(|_| error::IdenticonError::EncodeImageError)?;
-        Ok(buffer)
+        self.export_data(ExportFormat::Jpeg)
     }
 }
 
 impl Default for Identicon {
     fn default() -> Self {
-        let theme = theme::default_theme();
         Self {
+            input: String::new(),
             hash: hash::hash_value(""),
-            border: 50,
-            size: 5,
-            scale: 500,
-            mirrored: true,
-            theme,
+            renderer: Renderer::default(),
         }
     }
 }
@@ -281,8 +553,20 @@ impl FromStr for Identicon {
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::sync::Arc;
 
-    use crate::{Identicon, color::RGB};
+    use crate::{
+        Identicon,
+        cell::{CellGap, CellStyle},
+        color::RGB,
+        config::Config,
+        error::IdenticonError,
+        hash::Hasher,
+        mask::Mask,
+        pattern::Pattern,
+        style::Style,
+        symmetry::Symmetry,
+    };
 
     #[test]
     fn consistency() {
@@ -297,7 +581,7 @@ mod tests {
         ];
 
         let image = Identicon::new("test");
-        let grid = crate::grid::generate_full_grid(image.size, &image.hash);
+        let grid = crate::grid::generate_full_grid(image.size(), image.size(), &image.hash);
         let color = crate::theme::default_theme()
             .main_color(&image.hash)
             .expect("could not get color");
@@ -307,6 +591,49 @@ mod tests {
         assert_eq!(expected_grid, grid);
     }
 
+    // Rendered by identicon-rs 7.1.0 with `Identicon::new(input).set_size(size)` and
+    // `export_png_data`, before the rendering was reworked, see tests/golden/README.md
+    const EVEN_SIZE_IMAGES: [(&str, u32, &[u8]); 4] = [
+        (
+            "identicon_rs",
+            4,
+            include_bytes!("../tests/golden/default-size4-identicon_rs.png"),
+        ),
+        (
+            "conways-glider",
+            4,
+            include_bytes!("../tests/golden/default-size4-conways-glider.png"),
+        ),
+        (
+            "identicon_rs",
+            6,
+            include_bytes!("../tests/golden/default-size6-identicon_rs.png"),
+        ),
+        (
+            "conways-glider",
+            6,
+            include_bytes!("../tests/golden/default-size6-conways-glider.png"),
+        ),
+    ];
+
+    #[test]
+    fn even_sizes_match_previous_release() {
+        for (input, size, golden) in EVEN_SIZE_IMAGES {
+            let expected = image::load_from_memory(golden).expect("golden image should decode");
+            let image = Identicon::new(input)
+                .set_size(size)
+                .expect("size should be valid")
+                .generate_image()
+                .expect("identicon should render");
+
+            assert_eq!(
+                expected.to_rgb8(),
+                image.to_rgb8(),
+                "{input} at size {size}"
+            );
+        }
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
@@ -364,11 +691,614 @@ mod tests {
         assert_eq!(identicon_chained.mirrored(), identicon_mutated.mirrored());
     }
 
+    #[test]
+    fn mirrored_maps_to_symmetry() {
+        let mut identicon = Identicon::new("test");
+        assert!(identicon.mirrored());
+        assert_eq!(Symmetry::Vertical, identicon.symmetry());
+
+        identicon.set_mirrored(false);
+        assert!(!identicon.mirrored());
+        assert_eq!(Symmetry::None, identicon.symmetry());
+
+        identicon.set_symmetry(Symmetry::Both);
+        assert!(identicon.mirrored());
+        identicon.set_symmetry(Symmetry::VerticalCentred);
+        assert!(identicon.mirrored());
+        identicon.set_symmetry(Symmetry::Horizontal);
+        assert!(!identicon.mirrored());
+    }
+
+    #[test]
+    fn symmetry_is_applied_to_image() {
+        let image = Identicon::new("test")
+            .set_border(0)
+            .set_pattern(Arc::new(crate::pattern::Bits))
+            .set_symmetry(Symmetry::Horizontal)
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+
+        for (x, y, pixel) in image.enumerate_pixels() {
+            assert_eq!(image.get_pixel(x, image.height() - 1 - y), pixel);
+        }
+    }
+
+    #[test]
+    fn rectangular_dimensions_work() {
+        let mut identicon = Identicon::new("test");
+        identicon
+            .set_scale_dimensions(600, 200)
+            .expect("scale should be valid")
+            .set_grid_dimensions(9, 3)
+            .expect("size should be valid");
+        assert_eq!((9, 3), identicon.grid_dimensions());
+        assert_eq!((600, 200), identicon.scale_dimensions());
+        assert_eq!(9, identicon.size());
+        assert_eq!(600, identicon.scale());
+
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image");
+        assert_eq!((700, 300), (image.width(), image.height()));
+
+        let image = identicon
+            .generate_image_with_size(70)
+            .expect("identicon should generate an image");
+        assert_eq!((70, 30), (image.width(), image.height()));
+
+        // The set_size and set_scale setters make both axes equal
+        identicon
+            .set_size(4)
+            .expect("size should be valid")
+            .set_scale(100)
+            .expect("scale should be valid");
+        assert_eq!((4, 4), identicon.grid_dimensions());
+        assert_eq!((100, 100), identicon.scale_dimensions());
+    }
+
+    #[test]
+    fn rectangular_dimensions_are_checked_per_axis() {
+        let mut identicon = Identicon::new("test");
+        identicon
+            .set_scale_dimensions(600, 20)
+            .expect("scale should be valid");
+
+        assert!(matches!(
+            identicon.set_grid_dimensions(5, 21).err(),
+            Some(IdenticonError::SizeTooLargeError {
+                size: 21,
+                scale: 20
+            })
+        ));
+        assert!(identicon.set_size(21).is_err());
+        assert_eq!((5, 5), identicon.grid_dimensions());
+
+        assert!(matches!(
+            identicon.set_scale_dimensions(4, 600).err(),
+            Some(IdenticonError::ScaleTooSmallError { scale: 4, size: 5 })
+        ));
+        assert_eq!((600, 20), identicon.scale_dimensions());
+    }
+
+    #[test]
+    fn cell_style_works() {
+        let mut identicon = Identicon::new("test");
+        identicon.set_border(0).set_cell_style(CellStyle::Circle);
+        assert_eq!(CellStyle::Circle, identicon.cell_style());
+
+        let grid = identicon
+            .renderer
+            .generate_grid(&identicon.hash)
+            .expect("identicon should generate a grid");
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        let main = identicon
+            .theme()
+            .main_color(&identicon.hash)
+            .expect("theme should have a main color");
+        let background = identicon
+            .theme()
+            .background_color(&identicon.hash)
+            .expect("theme should have a background color");
+        let main = image::Rgb([main.red, main.green, main.blue]);
+        let background = image::Rgb([background.red, background.green, background.blue]);
+
+        // Active cells are drawn as circles, with anti-aliased edges
+        let location = grid
+            .iter()
+            .position(|active| *active)
+            .expect("grid should have an active cell") as u32;
+        let (x, y) = ((location % 5) * 100, (location / 5) * 100);
+        assert_eq!(main, *image.get_pixel(x + 50, y + 50));
+        assert_eq!(background, *image.get_pixel(x, y));
+        assert!(
+            image
+                .pixels()
+                .any(|pixel| *pixel != main && *pixel != background)
+        );
+
+        let svg = identicon
+            .generate_svg()
+            .expect("identicon should generate an svg");
+        assert_eq!(
+            grid.iter().filter(|active| **active).count(),
+            svg.matches("<ellipse").count()
+        );
+    }
+
+    #[test]
+    fn cell_gap_works() {
+        let mut identicon = Identicon::new("test");
+        identicon
+            .set_border(0)
+            .set_pattern(Arc::new(crate::pattern::Bits))
+            .set_cell_gap(CellGap::Pixels(10));
+        assert_eq!(CellGap::Pixels(10), identicon.cell_gap());
+
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        assert_eq!((500, 500), image.dimensions());
+
+        // The gaps between the cells have the background color
+        let background = identicon
+            .theme()
+            .background_color(&identicon.hash)
+            .expect("theme should have a background color");
+        let background = image::Rgb([background.red, background.green, background.blue]);
+        let spans = crate::grid::cell_spans(5, 500, 10);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let in_cell = |pixel| spans.iter().any(|span| span.contains(&pixel));
+            if !in_cell(x) || !in_cell(y) {
+                assert_eq!(background, *pixel);
+            }
+        }
+
+        // The outer cells still reach the edges
+        let grid = identicon
+            .renderer
+            .generate_grid(&identicon.hash)
+            .expect("identicon should generate a grid");
+        let last = grid
+            .iter()
+            .rposition(|active| *active)
+            .expect("grid should have an active cell") as u32;
+        let (x, y) = (
+            spans[(last % 5) as usize].end - 1,
+            spans[(last / 5) as usize].end - 1,
+        );
+        assert_ne!(background, *image.get_pixel(x, y));
+    }
+
+    #[test]
+    fn geometric_style_works() {
+        let mut identicon = Identicon::new("test");
+        let grid_image = identicon
+            .generate_image()
+            .expect("identicon should generate an image");
+
+        identicon.set_border(20).set_style(Style::Geometric);
+        assert_eq!(Style::Geometric, identicon.style());
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        assert_eq!((540, 540), image.dimensions());
+        assert_ne!(grid_image.to_rgb8(), image);
+
+        // The border has the background color and the shapes use the main color
+        let main = identicon
+            .theme()
+            .main_color(&identicon.hash)
+            .expect("theme should have a main color");
+        let background = identicon
+            .theme()
+            .background_color(&identicon.hash)
+            .expect("theme should have a background color");
+        let main = image::Rgb([main.red, main.green, main.blue]);
+        let background = image::Rgb([background.red, background.green, background.blue]);
+        assert_eq!(background, *image.get_pixel(10, 270));
+        assert!(image.pixels().any(|pixel| *pixel == main));
+
+        let svg = identicon
+            .generate_svg()
+            .expect("identicon should generate an svg");
+        assert!(svg.contains(r#"fill-rule="evenodd""#));
+        assert_eq!(16, svg.matches("<path").count());
+
+        // The default style is unchanged
+        identicon.set_border(50).set_style(Style::Grid);
+        assert_eq!(
+            grid_image,
+            identicon
+                .generate_image()
+                .expect("identicon should generate an image")
+        );
+    }
+
+    #[test]
+    fn mask_works() {
+        let mut identicon = Identicon::new("test");
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image");
+        assert!(!image.color().has_alpha());
+
+        identicon.set_mask(Mask::Circle);
+        assert_eq!(Mask::Circle, identicon.mask());
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgba8();
+        assert_eq!(0, image.get_pixel(0, 0)[3]);
+        assert_eq!(255, image.get_pixel(300, 300)[3]);
+
+        identicon.set_mask(Mask::RoundedRectangle { radius: 300 });
+        let image = identicon
+            .generate_image_with_size(60)
+            .expect("identicon should generate an image")
+            .to_rgba8();
+        assert_eq!(0, image.get_pixel(0, 0)[3]);
+        assert_eq!(255, image.get_pixel(30, 30)[3]);
+    }
+
+    #[test]
+    fn config_works() {
+        let mut identicon = Identicon::new("test");
+        let config = Config {
+            border: 1,
+            grid_dimensions: (8, 4),
+            scale_dimensions: (80, 40),
+            symmetry: Symmetry::Rotational4,
+            cell_style: CellStyle::Diamond,
+            cell_gap: CellGap::Fraction(0.1),
+            mask: Mask::RoundedRectangle { radius: 10 },
+            style: Style::Geometric,
+        };
+        identicon
+            .set_config(&config)
+            .expect("config should be valid");
+        assert_eq!(config, identicon.config());
+
+        let invalid = Config {
+            grid_dimensions: (8, 100),
+            ..config
+        };
+        assert!(identicon.set_config(&invalid).is_err());
+        assert_eq!(config, identicon.config());
+    }
+
+    #[test]
+    fn default_config_matches_default_identicon() {
+        assert_eq!(Config::default(), Identicon::default().config());
+    }
+
     #[test]
     fn getters_work() {
         let identicon = Identicon::new("test").set_border(10).clone();
 
-        assert_eq!(identicon.border(), identicon.border);
+        assert_eq!(identicon.border(), identicon.renderer.border);
+    }
+
+    #[test]
+    fn svg_matches_grid() {
+        let identicon = Identicon::new("test");
+        let svg = String::from_utf8(
+            identicon
+                .export_svg_data()
+                .expect("identicon should export svg data"),
+        )
+        .expect("svg should be valid utf-8");
+
+        let active_cells = identicon
+            .renderer
+            .generate_grid(&identicon.hash)
+            .expect("identicon should generate a grid")
+            .iter()
+            .filter(|a| **a)
+            .count();
+
+        assert!(svg.contains(r#"width="600" height="600""#));
+        // One rect for the background and one per active cell
+        assert_eq!(active_cells + 1, svg.matches("<rect").count());
+    }
+
+    #[test]
+    fn uneven_scale_spreads_leftover_pixels() {
+        let image = Identicon::new("test")
+            .set_border(0)
+            .set_mirrored(false)
+            .set_pattern(Arc::new(crate::pattern::Bits))
+            .set_scale(128)
+            .expect("scale should be valid")
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        assert_eq!(128, image.width());
+
+        // Count the cell widths along each row by finding the color changes
+        let boundaries = crate::grid::cell_boundaries(5, 128);
+        for y in 0..128 {
+            for x in 1..128 {
+                if image.get_pixel(x, y) != image.get_pixel(x - 1, y) {
+                    assert!(boundaries.contains(&x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generate_image_with_size_works() {
+        let identicon = Identicon::new("test");
+        let image = identicon
+            .generate_image_with_size(60)
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        let full_image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+
+        // The image is the full image scaled down by 10
+        assert_eq!(60, image.width());
+        for (x, y, pixel) in image.enumerate_pixels() {
+            assert_eq!(full_image.get_pixel(x * 10 + 5, y * 10 + 5), pixel);
+        }
+
+        assert!(identicon.generate_image_with_size(4).is_err());
+    }
+
+    #[cfg(feature = "ico")]
+    #[test]
+    fn export_ico_data_works() {
+        let data = Identicon::new("test")
+            .export_ico_data(&[16, 32, 48, 64])
+            .expect("identicon should export ico data");
+
+        // The ICO header lists the number of images, and the decoder picks the largest one
+        assert_eq!(4, u16::from_le_bytes([data[4], data[5]]));
+        let image = image::load_from_memory_with_format(&data, image::ImageFormat::Ico)
+            .expect("ico should decode");
+        assert_eq!(64, image.width());
+
+        assert!(Identicon::new("test").export_ico_data(&[512]).is_err());
+    }
+
+    #[cfg(feature = "ico")]
+    #[test]
+    fn export_favicon_set_works() {
+        let files = Identicon::new("test")
+            .export_favicon_set()
+            .expect("identicon should export the favicon set");
+        let names: Vec<_> = files.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            vec![
+                "favicon.ico",
+                "favicon-16x16.png",
+                "favicon-32x32.png",
+                "apple-touch-icon.png",
+                "android-chrome-192x192.png",
+                "android-chrome-512x512.png"
+            ],
+            names
+        );
+
+        let (_, apple_touch_icon) = &files[3];
+        let image = image::load_from_memory(apple_touch_icon).expect("png should decode");
+        assert_eq!(180, image.width());
+    }
+
+    #[test]
+    fn opaque_image_is_rgb() {
+        let image = Identicon::new("test")
+            .generate_image()
+            .expect("identicon should generate an image");
+        assert_eq!(image::ColorType::Rgb8, image.color());
+    }
+
+    #[test]
+    fn transparent_background_works() {
+        let image = Identicon::new("test")
+            .set_background_alpha(Some(0))
+            .generate_image()
+            .expect("identicon should generate an image");
+        assert_eq!(image::ColorType::Rgba8, image.color());
+
+        let image = image.to_rgba8();
+        // Border pixel
+        assert_eq!(0, image.get_pixel(0, 0)[3]);
+        // Active cell pixel
+        assert_eq!(255, image.get_pixel(50, 50)[3]);
+    }
+
+    #[test]
+    fn transparent_png_keeps_alpha() {
+        let data = Identicon::new("test")
+            .set_background_alpha(Some(0))
+            .export_png_data()
+            .expect("identicon should export png data");
+        let image = image::load_from_memory(&data).expect("png should decode");
+        assert_eq!(image::ColorType::Rgba8, image.color());
+        assert_eq!(0, image.to_rgba8().get_pixel(0, 0)[3]);
+    }
+
+    #[test]
+    fn default_hasher_is_unchanged() {
+        let identicon = Identicon::new("test");
+        assert_eq!(crate::hash::hash_value("test"), identicon.hash);
+    }
+
+    #[test]
+    fn set_hasher_regenerates_hash() {
+        struct ReverseHasher;
+
+        impl Hasher for ReverseHasher {
+            fn hash(&self, input: &[u8]) -> Vec<u8> {
+                input.iter().rev().copied().collect()
+            }
+        }
+
+        let mut identicon = Identicon::new(" test ");
+        identicon.set_hasher(Arc::new(ReverseHasher));
+        assert_eq!(b"tset".to_vec(), identicon.hash);
+
+        identicon.set_input("abc");
+        assert_eq!(b"cba".to_vec(), identicon.hash);
+    }
+
+    #[test]
+    fn empty_hash_is_rejected() {
+        struct EmptyHasher;
+
+        impl Hasher for EmptyHasher {
+            fn hash(&self, _input: &[u8]) -> Vec<u8> {
+                Vec::new()
+            }
+        }
+
+        let mut identicon = Identicon::new("test");
+        identicon.set_hasher(Arc::new(EmptyHasher));
+        assert!(matches!(
+            identicon.generate_image(),
+            Err(IdenticonError::EmptyHashError)
+        ));
+        assert!(matches!(
+            identicon.generate_svg(),
+            Err(IdenticonError::EmptyHashError)
+        ));
+        assert!(matches!(
+            identicon.export_png_data(),
+            Err(IdenticonError::EmptyHashError)
+        ));
+
+        let renderer = identicon.renderer();
+        assert!(matches!(
+            renderer.render("test"),
+            Err(IdenticonError::EmptyHashError)
+        ));
+        assert!(matches!(
+            renderer.encode("test", crate::renderer::ExportFormat::Png),
+            Err(IdenticonError::EmptyHashError)
+        ));
+    }
+
+    #[test]
+    fn custom_pattern_works() {
+        struct Checkerboard;
+
+        impl Pattern for Checkerboard {
+            fn grid(&self, _hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+                (0..width * height)
+                    .map(|location| location % 2 == 0)
+                    .collect()
+            }
+        }
+
+        let identicon = Identicon::new("test")
+            .set_mirrored(false)
+            .set_pattern(Arc::new(Checkerboard))
+            .clone();
+        let grid = identicon
+            .renderer
+            .generate_grid(&identicon.hash)
+            .expect("identicon should generate a grid");
+        assert_eq!(Checkerboard.grid(&[], 5, 5), grid);
+    }
+
+    #[test]
+    fn invalid_pattern_fails() {
+        struct Empty;
+
+        impl Pattern for Empty {
+            fn grid(&self, _hash: &[u8], _width: u32, _height: u32) -> Vec<bool> {
+                Vec::new()
+            }
+        }
+
+        let result = Identicon::new("test")
+            .set_pattern(Arc::new(Empty))
+            .generate_image();
+        assert!(result.is_err());
+
+        struct OutOfPalette;
+
+        impl Pattern for OutOfPalette {
+            fn grid(&self, _hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+                vec![true; (width * height) as usize]
+            }
+
+            fn palette_grid(
+                &self,
+                _hash: &[u8],
+                width: u32,
+                height: u32,
+                _colors: usize,
+            ) -> Vec<usize> {
+                vec![2; (width * height) as usize]
+            }
+        }
+
+        let result = Identicon::new("test")
+            .set_pattern(Arc::new(OutOfPalette))
+            .generate_image();
+        assert!(matches!(
+            result,
+            Err(IdenticonError::PaletteIndexError {
+                index: 2,
+                colors: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn palette_works() {
+        let mut theme = crate::theme::HSLRange::new(
+            0.0,
+            360.0,
+            50.0,
+            75.0,
+            40.0,
+            60.0,
+            vec![(240, 240, 240).into()],
+        )
+        .expect("theme should be valid");
+        theme.set_palette_size(3);
+
+        let mut identicon = Identicon::new("test");
+        identicon
+            .set_border(0)
+            .set_theme(Arc::new(theme))
+            .set_pattern(Arc::new(crate::pattern::MultiTone));
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+
+        // The background and all three palette colors are used
+        let mut colors = image.pixels().copied().collect::<Vec<_>>();
+        colors.sort_by_key(|pixel| pixel.0);
+        colors.dedup();
+        assert_eq!(4, colors.len());
+
+        let svg = identicon
+            .generate_svg()
+            .expect("identicon should generate an svg");
+        assert_eq!(3, svg.matches("<g fill").count());
+
+        // Two-tone patterns keep using only the main color
+        identicon.set_pattern(Arc::new(crate::pattern::EvenBytes));
+        let image = identicon
+            .generate_image()
+            .expect("identicon should generate an image")
+            .to_rgb8();
+        let mut colors = image.pixels().copied().collect::<Vec<_>>();
+        colors.sort_by_key(|pixel| pixel.0);
+        colors.dedup();
+        assert_eq!(2, colors.len());
     }
 
     #[test]
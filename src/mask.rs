@@ -0,0 +1,198 @@
+use std::fmt::Write;
+
+use image::RgbaImage;
+
+use crate::grid;
+
+/// The number of points used to draw a squircle in SVG.
+const SQUIRCLE_POINTS: u32 = 64;
+
+/// Shape mask applied to the full identicon image, including the border.
+///
+/// Pixels outside of the shape are transparent and the edges are anti-aliased, so any masked image
+/// is RGBA.
+/// On rectangular images the shapes are stretched to fill the image.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mask {
+    /// No mask, the full image is visible.
+    ///
+    /// This is the default.
+    #[default]
+    None,
+
+    /// A circle touching the edges of the image.
+    Circle,
+
+    /// A rectangle with rounded corners.
+    RoundedRectangle {
+        /// The corner radius in pixels, limited to half of the shorter side of the image.
+        radius: u32,
+    },
+
+    /// A squircle, the superellipse `|x|^4 + |y|^4 = 1`, touching the edges of the image.
+    Squircle,
+}
+
+impl Mask {
+    /// Checks if the point at `x`, `y` is within the shape covering a `width` x `height` image.
+    fn contains(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        let (half_width, half_height) = (width / 2.0, height / 2.0);
+        let (dx, dy) = ((x - half_width).abs(), (y - half_height).abs());
+
+        match self {
+            Mask::None => true,
+            Mask::Circle => (dx / half_width).powi(2) + (dy / half_height).powi(2) <= 1.0,
+            Mask::RoundedRectangle { radius } => {
+                let radius = (*radius as f32).min(half_width).min(half_height);
+                let corner_dx = (dx - (half_width - radius)).max(0.0);
+                let corner_dy = (dy - (half_height - radius)).max(0.0);
+                corner_dx.powi(2) + corner_dy.powi(2) <= radius.powi(2)
+            }
+            Mask::Squircle => (dx / half_width).powi(4) + (dy / half_height).powi(4) <= 1.0,
+        }
+    }
+
+    /// Scales the pixel lengths of the mask.
+    pub(crate) fn resize(&self, resize: impl Fn(u32) -> u32) -> Mask {
+        match self {
+            Mask::RoundedRectangle { radius } => Mask::RoundedRectangle {
+                radius: resize(*radius),
+            },
+            mask => *mask,
+        }
+    }
+
+    /// Makes the pixels outside of the shape transparent.
+    ///
+    /// The alpha of the edge pixels is multiplied by their coverage.
+    pub(crate) fn apply(&self, image: &mut RgbaImage) {
+        if *self == Mask::None {
+            return;
+        }
+
+        let (width, height) = image.dimensions();
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            let coverage = grid::pixel_coverage(x as f32, y as f32, |x, y| {
+                self.contains(x, y, width as f32, height as f32)
+            });
+            pixel[3] = (pixel[3] as f32 * coverage).round() as u8;
+        }
+    }
+
+    /// Gets the SVG element drawing the shape covering a `width` x `height` image.
+    ///
+    /// Returns `None` when there is no mask.
+    pub(crate) fn svg_shape(&self, width: u32, height: u32) -> Option<String> {
+        let (half_width, half_height) = (width as f32 / 2.0, height as f32 / 2.0);
+
+        match self {
+            Mask::None => None,
+            Mask::Circle => Some(format!(
+                r#"<ellipse cx="{half_width}" cy="{half_height}" rx="{half_width}" ry="{half_height}"/>"#
+            )),
+            Mask::RoundedRectangle { radius } => {
+                let radius = (*radius as f32).min(half_width).min(half_height);
+                Some(format!(
+                    r#"<rect width="{width}" height="{height}" rx="{radius}" ry="{radius}"/>"#
+                ))
+            }
+            Mask::Squircle => {
+                let mut path = String::new();
+                for point in 0..SQUIRCLE_POINTS {
+                    let angle = point as f32 * std::f32::consts::TAU / SQUIRCLE_POINTS as f32;
+                    let (sin, cos) = angle.sin_cos();
+                    let x = half_width + half_width * cos.signum() * cos.abs().sqrt();
+                    let y = half_height + half_height * sin.signum() * sin.abs().sqrt();
+                    let command = if point == 0 { 'M' } else { 'L' };
+                    // Writing to a String can not fail
+                    let _ = write!(path, "{command}{x:.2} {y:.2}");
+                }
+                Some(format!(r#"<path d="{path}Z"/>"#))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::RgbaImage;
+
+    use crate::mask::Mask;
+
+    fn masked(mask: Mask, width: u32, height: u32) -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(width, height, image::Rgba([1, 2, 3, 255]));
+        mask.apply(&mut image);
+        image
+    }
+
+    #[test]
+    fn none_keeps_image() {
+        let image = masked(Mask::None, 10, 10);
+        assert!(image.pixels().all(|pixel| pixel[3] == 255));
+    }
+
+    #[test]
+    fn circle_works() {
+        let image = masked(Mask::Circle, 100, 100);
+        assert_eq!(0, image.get_pixel(0, 0)[3]);
+        assert_eq!(0, image.get_pixel(99, 99)[3]);
+        assert_eq!(255, image.get_pixel(50, 50)[3]);
+        assert_eq!(255, image.get_pixel(50, 1)[3]);
+
+        // The edges are anti-aliased
+        assert!(image.pixels().any(|pixel| pixel[3] > 0 && pixel[3] < 255));
+        assert_eq!([1, 2, 3], image.get_pixel(0, 0).0[..3]);
+    }
+
+    #[test]
+    fn rounded_rectangle_works() {
+        let image = masked(Mask::RoundedRectangle { radius: 20 }, 100, 50);
+        assert_eq!(0, image.get_pixel(0, 0)[3]);
+        assert_eq!(0, image.get_pixel(99, 49)[3]);
+        assert_eq!(255, image.get_pixel(20, 0)[3]);
+        assert_eq!(255, image.get_pixel(0, 20)[3]);
+        assert_eq!(255, image.get_pixel(10, 10)[3]);
+
+        let image = masked(Mask::RoundedRectangle { radius: 0 }, 10, 10);
+        assert!(image.pixels().all(|pixel| pixel[3] == 255));
+    }
+
+    #[test]
+    fn squircle_works() {
+        let circle = masked(Mask::Circle, 100, 100);
+        let squircle = masked(Mask::Squircle, 100, 100);
+        assert_eq!(0, squircle.get_pixel(0, 0)[3]);
+
+        // A squircle covers more of the corners than a circle
+        assert_eq!(0, circle.get_pixel(12, 12)[3]);
+        assert_eq!(255, squircle.get_pixel(12, 12)[3]);
+    }
+
+    #[test]
+    fn resize_works() {
+        let mask = Mask::RoundedRectangle { radius: 20 };
+        assert_eq!(
+            Mask::RoundedRectangle { radius: 10 },
+            mask.resize(|length| length / 2)
+        );
+        assert_eq!(Mask::Circle, Mask::Circle.resize(|length| length / 2));
+    }
+
+    #[test]
+    fn svg_shape_works() {
+        assert_eq!(None, Mask::None.svg_shape(10, 10));
+        assert_eq!(
+            Some(r#"<ellipse cx="5" cy="10" rx="5" ry="10"/>"#.to_string()),
+            Mask::Circle.svg_shape(10, 20)
+        );
+        assert_eq!(
+            Some(r#"<rect width="10" height="20" rx="5" ry="5"/>"#.to_string()),
+            Mask::RoundedRectangle { radius: 8 }.svg_shape(10, 20)
+        );
+        let squircle = Mask::Squircle
+            .svg_shape(10, 10)
+            .expect("squircle should have a shape");
+        assert!(squircle.starts_with(r#"<path d="M10.00 5.00L"#));
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crate::grid;
+
+/// Trait defining how the hash is turned into the grid of an identicon
+///
+/// Symmetry is applied by the [`Identicon`](crate::Identicon) after the grid is generated.
+pub trait Pattern {
+    /// This should return `width * height` cells in row-major order, where `true` is an active
+    /// cell
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool>;
+
+    /// This should return `width * height` palette indices in row-major order
+    ///
+    /// Index 0 is the background and index `i` is color `i - 1` of the
+    /// [Theme::palette](crate::theme::Theme::palette), which has `colors` colors.
+    ///
+    /// The default implementation uses [Pattern::grid], so every active cell uses the main color.
+    fn palette_grid(&self, hash: &[u8], width: u32, height: u32, colors: usize) -> Vec<usize> {
+        let _ = colors;
+        self.grid(hash, width, height)
+            .into_iter()
+            .map(usize::from)
+            .collect()
+    }
+}
+
+/// Even byte pattern struct
+///
+/// Each cell uses one byte of the hash and is active when that byte is even.
+/// The hash is repeated when there are more cells than bytes.
+///
+/// Implements [Pattern]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EvenBytes;
+
+impl Pattern for EvenBytes {
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+        grid::generate_full_grid(width, height, hash)
+    }
+}
+
+/// Bit pattern struct
+///
+/// Each cell uses one bit of the hash and is active when that bit is set.
+/// The hash is extended with SHAKE256 when there are more cells than bits, so large grids do not
+/// repeat.
+///
+/// Implements [Pattern]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bits;
+
+impl Pattern for Bits {
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+        grid::generate_bit_grid(width, height, hash)
+    }
+}
+
+/// Nibble pattern struct
+///
+/// Each cell uses one nibble of the hash and is active when that nibble is even.
+/// The nibbles fill the columns from the middle column to the left edge, each from top to bottom,
+/// and are mirrored onto the right half, as GitHub's identicons do.
+/// Cells are inactive once the hash runs out of nibbles.
+///
+/// Implements [Pattern]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Nibbles;
+
+impl Pattern for Nibbles {
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+        grid::generate_nibble_grid(width, height, hash)
+    }
+}
+
+/// Multi-tone pattern struct
+///
+/// Each cell uses one byte of the hash to pick the background or one of the palette colors, with
+/// every choice equally likely.
+/// The hash is extended with SHAKE256 when there are more cells than bytes, so large grids do not
+/// repeat.
+///
+/// Combined with a theme palette of 2 or 3 colors, this renders three- and four-tone identicons.
+/// As a two-tone [Pattern::grid], half of the cells are active.
+///
+/// Implements [Pattern]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MultiTone;
+
+impl Pattern for MultiTone {
+    fn grid(&self, hash: &[u8], width: u32, height: u32) -> Vec<bool> {
+        self.palette_grid(hash, width, height, 1)
+            .into_iter()
+            .map(|index| index != 0)
+            .collect()
+    }
+
+    fn palette_grid(&self, hash: &[u8], width: u32, height: u32, colors: usize) -> Vec<usize> {
+        let square_count = (width * height) as usize;
+        grid::extend_hash(hash, square_count)
+            .iter()
+            .take(square_count)
+            .map(|byte| *byte as usize * (colors + 1) / 256)
+            .collect()
+    }
+}
+
+/// The default pattern
+///
+/// This is [EvenBytes], the original pattern before patterns were customizable.
+pub fn default_pattern() -> Arc<dyn Pattern + Send + Sync> {
+    Arc::new(EvenBytes)
+}
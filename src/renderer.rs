@@ -0,0 +1,710 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::{DynamicImage, ImageBuffer, RgbaImage};
+
+use crate::cell::{self, CellGap, CellStyle, Direction};
+use crate::color::RGBA;
+use crate::error::IdenticonError;
+use crate::geometric::{self, Tile};
+use crate::hash::{self, Hasher};
+use crate::mask::Mask;
+use crate::pattern::{self, Pattern};
+use crate::style::Style;
+use crate::symmetry::Symmetry;
+use crate::theme::{self, Theme};
+use crate::{grid, svg};
+
+/// Image formats an identicon can be encoded to.
+///
+/// Some formats require the cargo feature of the same name.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ExportFormat {
+    /// PNG, preserving the alpha channel of transparent identicons.
+    Png,
+
+    /// JPEG, which has no alpha channel.
+    Jpeg,
+
+    /// SVG, with one `<rect>` per active cell.
+    Svg,
+
+    /// Lossless WebP.
+    #[cfg(feature = "webp")]
+    WebP,
+
+    /// GIF.
+    #[cfg(feature = "gif")]
+    Gif,
+
+    /// BMP.
+    #[cfg(feature = "bmp")]
+    Bmp,
+
+    /// ICO, which is limited to images of at most 256x256 pixels.
+    #[cfg(feature = "ico")]
+    Ico,
+
+    /// AVIF.
+    #[cfg(feature = "avif")]
+    Avif,
+
+    /// QOI.
+    #[cfg(feature = "qoi")]
+    Qoi,
+}
+
+impl ExportFormat {
+    /// Gets the format for a file extension, ignoring case.
+    ///
+    /// Returns `None` for unknown extensions and formats whose feature is disabled.
+    pub fn from_extension(extension: &str) -> Option<ExportFormat> {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" => Some(ExportFormat::Png),
+            "jpg" | "jpeg" => Some(ExportFormat::Jpeg),
+            "svg" => Some(ExportFormat::Svg),
+            #[cfg(feature = "webp")]
+            "webp" => Some(ExportFormat::WebP),
+            #[cfg(feature = "gif")]
+            "gif" => Some(ExportFormat::Gif),
+            #[cfg(feature = "bmp")]
+            "bmp" => Some(ExportFormat::Bmp),
+            #[cfg(feature = "ico")]
+            "ico" => Some(ExportFormat::Ico),
+            #[cfg(feature = "avif")]
+            "avif" => Some(ExportFormat::Avif),
+            #[cfg(feature = "qoi")]
+            "qoi" => Some(ExportFormat::Qoi),
+            _ => None,
+        }
+    }
+
+    /// Gets the usual file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Png => "png",
+            ExportFormat::Jpeg => "jpg",
+            ExportFormat::Svg => "svg",
+            #[cfg(feature = "webp")]
+            ExportFormat::WebP => "webp",
+            #[cfg(feature = "gif")]
+            ExportFormat::Gif => "gif",
+            #[cfg(feature = "bmp")]
+            ExportFormat::Bmp => "bmp",
+            #[cfg(feature = "ico")]
+            ExportFormat::Ico => "ico",
+            #[cfg(feature = "avif")]
+            ExportFormat::Avif => "avif",
+            #[cfg(feature = "qoi")]
+            ExportFormat::Qoi => "qoi",
+        }
+    }
+
+    /// Gets the MIME type of the format, e.g. for a `Content-Type` header.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Png => "image/png",
+            ExportFormat::Jpeg => "image/jpeg",
+            ExportFormat::Svg => "image/svg+xml",
+            #[cfg(feature = "webp")]
+            ExportFormat::WebP => "image/webp",
+            #[cfg(feature = "gif")]
+            ExportFormat::Gif => "image/gif",
+            #[cfg(feature = "bmp")]
+            ExportFormat::Bmp => "image/bmp",
+            #[cfg(feature = "ico")]
+            ExportFormat::Ico => "image/x-icon",
+            #[cfg(feature = "avif")]
+            ExportFormat::Avif => "image/avif",
+            #[cfg(feature = "qoi")]
+            ExportFormat::Qoi => "image/qoi",
+        }
+    }
+}
+
+/// Reusable identicon renderer.
+///
+/// A renderer holds all of the identicon settings, but not the input value, so a single renderer
+/// can generate identicons for any number of inputs.
+/// It is immutable, `Send` and `Sync`, so it can be shared between threads without locking.
+///
+/// A renderer is built by configuring an [`Identicon`](crate::Identicon) and calling
+/// [`Identicon::renderer`](crate::Identicon::renderer).
+#[derive(Clone)]
+pub struct Renderer {
+    pub(crate) border: u32,
+    pub(crate) grid_width: u32,
+    pub(crate) grid_height: u32,
+    pub(crate) scale_width: u32,
+    pub(crate) scale_height: u32,
+    pub(crate) symmetry: Symmetry,
+    pub(crate) cell_style: CellStyle,
+    pub(crate) cell_gap: CellGap,
+    pub(crate) background_alpha: Option<u8>,
+    pub(crate) mask: Mask,
+    pub(crate) style: Style,
+    pub(crate) theme: Arc<dyn Theme + Send + Sync>,
+    pub(crate) hasher: Arc<dyn Hasher + Send + Sync>,
+    pub(crate) pattern: Arc<dyn Pattern + Send + Sync>,
+}
+
+impl Renderer {
+    /// Gets the border size.
+    pub fn border(&self) -> u32 {
+        self.border
+    }
+
+    /// Gets the number of viewable blocks.
+    ///
+    /// For rectangular grids this is the width, see [`Renderer::grid_dimensions`].
+    pub fn size(&self) -> u32 {
+        self.grid_width
+    }
+
+    /// Gets the width and height of the grid, in blocks.
+    pub fn grid_dimensions(&self) -> (u32, u32) {
+        (self.grid_width, self.grid_height)
+    }
+
+    /// Gets the scale.
+    ///
+    /// For rectangular images this is the width, see [`Renderer::scale_dimensions`].
+    pub fn scale(&self) -> u32 {
+        self.scale_width
+    }
+
+    /// Gets the width and height of the identicon portion of the image.
+    pub fn scale_dimensions(&self) -> (u32, u32) {
+        (self.scale_width, self.scale_height)
+    }
+
+    /// Gets the symmetry.
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    /// Gets the cell style.
+    pub fn cell_style(&self) -> CellStyle {
+        self.cell_style
+    }
+
+    /// Gets the gap between cells.
+    pub fn cell_gap(&self) -> CellGap {
+        self.cell_gap
+    }
+
+    /// Gets the background alpha override.
+    pub fn background_alpha(&self) -> Option<u8> {
+        self.background_alpha
+    }
+
+    /// Gets the mask.
+    pub fn mask(&self) -> Mask {
+        self.mask
+    }
+
+    /// Gets the render style.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Gets the theme.
+    pub fn theme(&self) -> Arc<dyn Theme> {
+        self.theme.clone()
+    }
+
+    /// Gets the hasher.
+    pub fn hasher(&self) -> Arc<dyn Hasher> {
+        self.hasher.clone()
+    }
+
+    /// Gets the pattern.
+    pub fn pattern(&self) -> Arc<dyn Pattern> {
+        self.pattern.clone()
+    }
+
+    /// Generates the DynamicImage representing the identicon for the input value.
+    ///
+    /// The input value is trimmed before it is hashed.
+    pub fn render(&self, input_value: &str) -> Result<DynamicImage, IdenticonError> {
+        self.generate_image(&self.hash(input_value)?)
+    }
+
+    /// Encodes the identicon for the input value in the given format.
+    ///
+    /// The input value is trimmed before it is hashed.
+    pub fn encode(
+        &self,
+        input_value: &str,
+        format: ExportFormat,
+    ) -> Result<Vec<u8>, IdenticonError> {
+        self.export_data(&self.hash(input_value)?, format)
+    }
+
+    /// Gets a copy of this renderer producing images of `image_size` pixels along the longer side.
+    ///
+    /// The border and scale are adjusted proportionally, with all lengths rounded to the nearest
+    /// pixel.
+    pub(crate) fn with_image_size(&self, image_size: u32) -> Result<Renderer, IdenticonError> {
+        let final_width = self.scale_width + (2 * self.border);
+        let final_height = self.scale_height + (2 * self.border);
+        let final_size = final_width.max(final_height).max(1) as u64;
+        let resize = |length: u32| {
+            ((2 * length as u64 * image_size as u64 + final_size) / (2 * final_size)) as u32
+        };
+
+        let border = resize(self.border);
+        let scale_width = resize(final_width).saturating_sub(2 * border);
+        let scale_height = resize(final_height).saturating_sub(2 * border);
+        for (scale, size) in [
+            (scale_width, self.grid_width),
+            (scale_height, self.grid_height),
+        ] {
+            if scale < size {
+                return Err(IdenticonError::ScaleTooSmallError { scale, size });
+            }
+        }
+
+        Ok(Renderer {
+            border,
+            scale_width,
+            scale_height,
+            cell_gap: self.cell_gap.resize(resize),
+            mask: self.mask.resize(resize),
+            ..self.clone()
+        })
+    }
+
+    /// Hashes the trimmed input value, rejecting empty hashes.
+    pub(crate) fn hash(&self, input_value: &str) -> Result<Vec<u8>, IdenticonError> {
+        let hash = self.hasher.hash(input_value.trim().as_bytes());
+        hash::check_hash(&hash)?;
+        Ok(hash)
+    }
+
+    /// Generates the grid of active cells, with symmetry applied.
+    #[cfg(test)]
+    pub(crate) fn generate_grid(&self, hash: &[u8]) -> Result<Vec<bool>, IdenticonError> {
+        let palette = self.theme.palette(hash)?;
+        Ok(self
+            .generate_palette_grid(hash, palette.len())?
+            .into_iter()
+            .map(|index| index != 0)
+            .collect())
+    }
+
+    /// Generates the grid of palette indices, with symmetry applied.
+    ///
+    /// Index 0 is the background and index `i` is color `i - 1` of a palette with `colors` colors.
+    pub(crate) fn generate_palette_grid(
+        &self,
+        hash: &[u8],
+        colors: usize,
+    ) -> Result<Vec<usize>, IdenticonError> {
+        let (width, height) = (self.grid_width, self.grid_height);
+        let grid = self.pattern.palette_grid(hash, width, height, colors);
+        let expected = (width * height) as usize;
+        if grid.len() != expected {
+            return Err(IdenticonError::PatternGridSizeError {
+                expected,
+                actual: grid.len(),
+            });
+        }
+        if let Some(index) = grid.iter().find(|index| **index > colors) {
+            return Err(IdenticonError::PaletteIndexError {
+                index: *index,
+                colors,
+            });
+        }
+
+        Ok((0..width * height)
+            .map(|location| {
+                let (x, y) =
+                    self.symmetry
+                        .source_cell(location % width, location / width, width, height);
+
+                // Get location within the generated grid
+                grid[(x + y * width) as usize]
+            })
+            .collect())
+    }
+
+    /// Gets the pixel spans of the columns and rows of cells, leaving the cell gap between them.
+    pub(crate) fn cell_spans(&self) -> (Vec<Range<u32>>, Vec<Range<u32>>) {
+        let span =
+            |cells, length| grid::cell_spans(cells, length, self.cell_gap.pixels(cells, length));
+        (
+            span(self.grid_width, self.scale_width),
+            span(self.grid_height, self.scale_height),
+        )
+    }
+
+    /// Gets the direction of every cell, which is only used by [`CellStyle::Triangle`].
+    fn cell_directions(&self, hash: &[u8]) -> Vec<Direction> {
+        let (width, height) = (self.grid_width, self.grid_height);
+        if self.cell_style == CellStyle::Triangle {
+            cell::triangle_directions(hash, width, height, self.symmetry)
+        } else {
+            vec![Direction::Up; (width * height) as usize]
+        }
+    }
+
+    /// Generates the tiles of the [`Style::Geometric`] style for a palette with `colors` colors.
+    fn generate_tiles(&self, hash: &[u8], colors: usize) -> Result<Vec<Tile>, IdenticonError> {
+        if colors == 0 {
+            return Err(IdenticonError::PaletteIndexError { index: 1, colors });
+        }
+        Ok(geometric::tiles(hash, colors))
+    }
+
+    /// Gets the background color from the theme, applying the background alpha override.
+    fn background_color_rgba(&self, hash: &[u8]) -> Result<RGBA, IdenticonError> {
+        let mut color = self.theme.background_color_rgba(hash)?;
+        if let Some(alpha) = self.background_alpha {
+            color.alpha = alpha;
+        }
+        Ok(color)
+    }
+
+    /// Generates the DynamicImage for the hash.
+    ///
+    /// The image is drawn directly at the output resolution, in the [`Style`] of the renderer.
+    pub(crate) fn generate_image(&self, hash: &[u8]) -> Result<DynamicImage, IdenticonError> {
+        // Create pixel objects
+        let palette = self.theme.palette(hash)?;
+        let color_background = self.background_color_rgba(hash)?;
+        let pixels_active = palette
+            .iter()
+            .map(|color| image::Rgba([color.red, color.green, color.blue, color.alpha]))
+            .collect::<Vec<_>>();
+        let pixel_background = image::Rgba([
+            color_background.red,
+            color_background.green,
+            color_background.blue,
+            color_background.alpha,
+        ]);
+
+        let mut image_buffer = match self.style {
+            Style::Grid => self.rasterize_grid(hash, &pixels_active, pixel_background)?,
+            Style::Geometric => self.rasterize_geometric(hash, &pixels_active, pixel_background)?,
+        };
+
+        self.mask.apply(&mut image_buffer);
+
+        // Only keep the alpha channel if the mask or any of the colors use it
+        let image = DynamicImage::ImageRgba8(image_buffer);
+        if self.mask == Mask::None
+            && palette.iter().all(RGBA::is_opaque)
+            && color_background.is_opaque()
+        {
+            Ok(DynamicImage::ImageRgb8(image.to_rgb8()))
+        } else {
+            Ok(image)
+        }
+    }
+
+    /// Draws the cells of the [`Style::Grid`] style, with their boundaries computed by
+    /// [`grid::cell_spans`].
+    fn rasterize_grid(
+        &self,
+        hash: &[u8],
+        pixels_active: &[image::Rgba<u8>],
+        pixel_background: image::Rgba<u8>,
+    ) -> Result<RgbaImage, IdenticonError> {
+        // Create a new grid
+        let grid = self.generate_palette_grid(hash, pixels_active.len())?;
+
+        // Map every pixel within the identicon portion of the image to a grid cell
+        let directions = self.cell_directions(hash);
+        let (x_spans, y_spans) = self.cell_spans();
+        let x_pixel_cells = grid::pixel_cells(&x_spans, self.scale_width);
+        let y_pixel_cells = grid::pixel_cells(&y_spans, self.scale_height);
+
+        // Rasterize the cells directly at the output resolution
+        let final_width = self.scale_width + (2 * self.border);
+        let final_height = self.scale_height + (2 * self.border);
+        Ok(ImageBuffer::from_fn(final_width, final_height, |x, y| {
+            let x_cell = x
+                .checked_sub(self.border)
+                .and_then(|x| x_pixel_cells.get(x as usize).copied().flatten());
+            let y_cell = y
+                .checked_sub(self.border)
+                .and_then(|y| y_pixel_cells.get(y as usize).copied().flatten());
+
+            // Set the pixel color based on the value within the grid at the given position
+            let (x_cell, y_cell) = match (x_cell, y_cell) {
+                (Some(x_cell), Some(y_cell)) => (x_cell as usize, y_cell as usize),
+                _ => return pixel_background,
+            };
+            let location = x_cell + y_cell * self.grid_width as usize;
+            let pixel_active = match grid[location] {
+                0 => return pixel_background,
+                index => pixels_active[index - 1],
+            };
+            if self.cell_style == CellStyle::Square {
+                return pixel_active;
+            }
+
+            // Blend the edges of other shapes with the background
+            let (x_span, y_span) = (&x_spans[x_cell], &y_spans[y_cell]);
+            let cell_x = x - self.border - x_span.start;
+            let cell_y = y - self.border - y_span.start;
+            let cell_width = x_span.len() as f32;
+            let cell_height = y_span.len() as f32;
+            let coverage =
+                grid::pixel_coverage(cell_x as f32, cell_y as f32, |sample_x, sample_y| {
+                    self.cell_style.contains(
+                        sample_x / cell_width,
+                        sample_y / cell_height,
+                        directions[location],
+                    )
+                });
+            blend(pixel_background, pixel_active, coverage)
+        }))
+    }
+
+    /// Draws the tiles of the [`Style::Geometric`] style, laid out by [`geometric::layout`].
+    fn rasterize_geometric(
+        &self,
+        hash: &[u8],
+        pixels_active: &[image::Rgba<u8>],
+        pixel_background: image::Rgba<u8>,
+    ) -> Result<RgbaImage, IdenticonError> {
+        let tiles = self.generate_tiles(hash, pixels_active.len())?;
+        let (tile_size, x_offset, y_offset) =
+            geometric::layout(self.scale_width, self.scale_height);
+
+        // Index the tiles by position, row by row
+        let mut tile_grid = vec![None; (geometric::TILES * geometric::TILES) as usize];
+        for tile in &tiles {
+            tile_grid[(tile.y * geometric::TILES + tile.x) as usize] = Some(tile);
+        }
+
+        let final_width = self.scale_width + (2 * self.border);
+        let final_height = self.scale_height + (2 * self.border);
+        Ok(ImageBuffer::from_fn(final_width, final_height, |x, y| {
+            let tile_x = x.checked_sub(self.border + x_offset);
+            let tile_y = y.checked_sub(self.border + y_offset);
+            let (tile_x, tile_y) = match (tile_x, tile_y) {
+                (Some(tile_x), Some(tile_y)) if tile_size > 0 => (tile_x, tile_y),
+                _ => return pixel_background,
+            };
+            let (column, row) = (tile_x / tile_size, tile_y / tile_size);
+            let tile = match tile_grid.get((row * geometric::TILES + column) as usize) {
+                Some(Some(tile)) if column < geometric::TILES => tile,
+                _ => return pixel_background,
+            };
+
+            // Blend the edges of the shapes with the background
+            let coverage = tile.pixel_coverage(
+                (tile_x % tile_size) as f32,
+                (tile_y % tile_size) as f32,
+                tile_size as f32,
+            );
+            blend(pixel_background, pixels_active[tile.color], coverage)
+        }))
+    }
+
+    /// Generates the SVG document for the hash.
+    pub(crate) fn generate_svg(&self, hash: &[u8]) -> Result<String, IdenticonError> {
+        let palette = self.theme.palette(hash)?;
+        let color_background = self.background_color_rgba(hash)?;
+
+        match self.style {
+            Style::Grid => Ok(svg::generate_svg(
+                self,
+                &self.generate_palette_grid(hash, palette.len())?,
+                &self.cell_directions(hash),
+                &palette,
+                color_background,
+            )),
+            Style::Geometric => Ok(svg::generate_geometric_svg(
+                self,
+                &self.generate_tiles(hash, palette.len())?,
+                &palette,
+                color_background,
+            )),
+        }
+    }
+
+    /// Encodes the identicon for the hash in the given format.
+    pub(crate) fn export_data(
+        &self,
+        hash: &[u8],
+        format: ExportFormat,
+    ) -> Result<Vec<u8>, IdenticonError> {
+        // SVG is the only format that is not rasterized
+        let image = || self.generate_image(hash);
+        let mut buffer = Vec::new();
+        let result = match format {
+            ExportFormat::Svg => return Ok(self.generate_svg(hash)?.into_bytes()),
+            ExportFormat::Png => image()?.write_with_encoder(PngEncoder::new(&mut buffer)),
+            // JPEG has no alpha channel
+            ExportFormat::Jpeg => DynamicImage::ImageRgb8(image()?.to_rgb8())
+                .write_with_encoder(JpegEncoder::new(&mut buffer)),
+            #[cfg(feature = "webp")]
+            ExportFormat::WebP => image()?
+                .write_with_encoder(image::codecs::webp::WebPEncoder::new_lossless(&mut buffer)),
+            #[cfg(feature = "gif")]
+            ExportFormat::Gif => {
+                image()?.write_with_encoder(image::codecs::gif::GifEncoder::new(&mut buffer))
+            }
+            #[cfg(feature = "bmp")]
+            ExportFormat::Bmp => {
+                image()?.write_with_encoder(image::codecs::bmp::BmpEncoder::new(&mut buffer))
+            }
+            #[cfg(feature = "ico")]
+            // ICO embeds its images as RGBA PNGs
+            ExportFormat::Ico => DynamicImage::ImageRgba8(image()?.to_rgba8())
+                .write_with_encoder(image::codecs::ico::IcoEncoder::new(&mut buffer)),
+            #[cfg(feature = "avif")]
+            ExportFormat::Avif => {
+                image()?.write_with_encoder(image::codecs::avif::AvifEncoder::new(&mut buffer))
+            }
+            #[cfg(feature = "qoi")]
+            ExportFormat::Qoi => {
+                image()?.write_with_encoder(image::codecs::qoi::QoiEncoder::new(&mut buffer))
+            }
+        };
+
+        result.map_err(|_| IdenticonError::EncodeImageError)?;
+        Ok(buffer)
+    }
+}
+
+/// Blends two pixels, where a coverage of 1 is fully the foreground.
+fn blend(
+    background: image::Rgba<u8>,
+    foreground: image::Rgba<u8>,
+    coverage: f32,
+) -> image::Rgba<u8> {
+    image::Rgba(std::array::from_fn(|channel| {
+        let background = background[channel] as f32;
+        let foreground = foreground[channel] as f32;
+        (background + (foreground - background) * coverage).round() as u8
+    }))
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            border: 50,
+            grid_width: 5,
+            grid_height: 5,
+            scale_width: 500,
+            scale_height: 500,
+            symmetry: Symmetry::default(),
+            cell_style: CellStyle::default(),
+            cell_gap: CellGap::default(),
+            background_alpha: None,
+            mask: Mask::default(),
+            style: Style::default(),
+            theme: theme::default_theme(),
+            hasher: hash::default_hasher(),
+            pattern: pattern::default_pattern(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Identicon;
+    use crate::renderer::{ExportFormat, Renderer};
+
+    fn assert_decodes(format: ExportFormat, image_format: image::ImageFormat, size: u32) {
+        let renderer = Identicon::default()
+            .set_border(size / 10)
+            .set_scale(size - 2 * (size / 10))
+            .expect("scale should be valid")
+            .renderer();
+        let data = renderer
+            .encode("test", format)
+            .expect("renderer should encode data");
+        let image =
+            image::load_from_memory_with_format(&data, image_format).expect("data should decode");
+        assert_eq!(size, image.width());
+    }
+
+    #[test]
+    fn raster_formats_work() {
+        assert_decodes(ExportFormat::Png, image::ImageFormat::Png, 100);
+        assert_decodes(ExportFormat::Jpeg, image::ImageFormat::Jpeg, 100);
+        #[cfg(feature = "webp")]
+        assert_decodes(ExportFormat::WebP, image::ImageFormat::WebP, 100);
+        #[cfg(feature = "gif")]
+        assert_decodes(ExportFormat::Gif, image::ImageFormat::Gif, 100);
+        #[cfg(feature = "bmp")]
+        assert_decodes(ExportFormat::Bmp, image::ImageFormat::Bmp, 100);
+        #[cfg(feature = "ico")]
+        assert_decodes(ExportFormat::Ico, image::ImageFormat::Ico, 100);
+        #[cfg(feature = "qoi")]
+        assert_decodes(ExportFormat::Qoi, image::ImageFormat::Qoi, 100);
+    }
+
+    #[test]
+    fn extensions_round_trip() {
+        for format in [ExportFormat::Png, ExportFormat::Jpeg, ExportFormat::Svg] {
+            assert_eq!(
+                Some(format),
+                ExportFormat::from_extension(format.extension())
+            );
+        }
+        assert_eq!(
+            Some(ExportFormat::Jpeg),
+            ExportFormat::from_extension("JPEG")
+        );
+        assert_eq!(None, ExportFormat::from_extension("txt"));
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Renderer>();
+    }
+
+    #[test]
+    fn render_matches_identicon() {
+        let renderer = Renderer::default();
+
+        for input in ["test", "  test  ", "conways-glider"] {
+            let rendered = renderer.render(input).expect("renderer should render");
+            let generated = Identicon::new(input)
+                .generate_image()
+                .expect("identicon should generate an image");
+            assert_eq!(rendered.as_bytes(), generated.as_bytes());
+        }
+    }
+
+    #[test]
+    fn encode_matches_identicon() {
+        let renderer = Identicon::new("ignored")
+            .set_border(0)
+            .set_mirrored(false)
+            .renderer();
+        let identicon = Identicon::new("test")
+            .set_border(0)
+            .set_mirrored(false)
+            .clone();
+
+        assert_eq!(
+            identicon
+                .export_png_data()
+                .expect("identicon should export png data"),
+            renderer
+                .encode("test", ExportFormat::Png)
+                .expect("renderer should encode png data")
+        );
+        assert_eq!(
+            identicon
+                .export_svg_data()
+                .expect("identicon should export svg data"),
+            renderer
+                .encode("test", ExportFormat::Svg)
+                .expect("renderer should encode svg data")
+        );
+    }
+}
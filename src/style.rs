@@ -0,0 +1,21 @@
+/// Overall rendering style of an identicon.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Style {
+    /// A grid of cells generated by the pattern, shaped by the cell style.
+    ///
+    /// This is the default.
+    #[default]
+    Grid,
+
+    /// Geometric shapes on a 4x4 layout of tiles, modeled on Jdenticon.
+    ///
+    /// The side, corner and center tiles each use one shape, picked and rotated by the hash, so
+    /// the icon has four-fold rotational symmetry.
+    /// Each group of tiles is colored with a palette color picked by the hash, so themes with a
+    /// [palette](crate::theme::Theme::palette) of 2 or 3 colors give the most variety.
+    ///
+    /// The grid size, symmetry, pattern, cell style and cell gap are not used.
+    /// The tiles fill the shorter side of the scale and are centered along the longer side.
+    Geometric,
+}
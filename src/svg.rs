@@ -0,0 +1,271 @@
+use std::fmt::Write;
+
+use crate::cell::{CellStyle, Direction};
+use crate::color::{RGB, RGBA};
+use crate::geometric::{self, Tile};
+use crate::mask::Mask;
+use crate::renderer::Renderer;
+
+/// Identicon SVG generation.
+///
+/// The background is drawn as a single rectangle covering the full image, including the border.
+/// Every active cell of the grid is drawn as one element in the cell style, using the same pixel
+/// spans as the raster output, within one group per palette color.
+/// The mask is applied as a clip path around everything, including the background.
+pub fn generate_svg(
+    renderer: &Renderer,
+    grid: &[usize],
+    directions: &[Direction],
+    palette: &[RGBA],
+    background_color: RGBA,
+) -> String {
+    let border = renderer.border;
+    let (x_spans, y_spans) = renderer.cell_spans();
+    let mut body = String::new();
+
+    for (color_index, color) in palette.iter().enumerate() {
+        // Writing to a String can not fail
+        let _ = write!(body, r#"<g{}>"#, fill(*color));
+
+        for (location, index) in grid.iter().enumerate() {
+            if *index == color_index + 1 {
+                let x = location % renderer.grid_width as usize;
+                let y = location / renderer.grid_width as usize;
+                body.push_str(&renderer.cell_style.svg_shape(
+                    border + x_spans[x].start,
+                    border + y_spans[y].start,
+                    x_spans[x].len() as u32,
+                    y_spans[y].len() as u32,
+                    directions[location],
+                ));
+            }
+        }
+
+        body.push_str("</g>");
+    }
+
+    // Only squares line up with the pixel grid
+    document(
+        renderer,
+        background_color,
+        renderer.cell_style == CellStyle::Square,
+        &body,
+    )
+}
+
+/// Geometric identicon SVG generation.
+///
+/// This is drawn like [`generate_svg`], with the shapes of the tiles as one even-odd filled path
+/// each, using the same layout as the raster output.
+pub fn generate_geometric_svg(
+    renderer: &Renderer,
+    tiles: &[Tile],
+    palette: &[RGBA],
+    background_color: RGBA,
+) -> String {
+    let (tile_size, x_offset, y_offset) =
+        geometric::layout(renderer.scale_width, renderer.scale_height);
+    let left = (renderer.border + x_offset) as f32;
+    let top = (renderer.border + y_offset) as f32;
+    let size = tile_size as f32;
+    let mut body = String::new();
+
+    for (color_index, color) in palette.iter().enumerate() {
+        // Writing to a String can not fail
+        let _ = write!(body, r#"<g{} fill-rule="evenodd">"#, fill(*color));
+
+        for tile in tiles.iter().filter(|tile| tile.color == color_index) {
+            let path = tile.svg_path(
+                left + tile.x as f32 * size,
+                top + tile.y as f32 * size,
+                size,
+            );
+            let _ = write!(body, r#"<path d="{path}"/>"#);
+        }
+
+        body.push_str("</g>");
+    }
+
+    document(renderer, background_color, false, &body)
+}
+
+/// Wraps the body in the SVG document, drawing the background below it and applying the mask.
+fn document(renderer: &Renderer, background_color: RGBA, crisp_edges: bool, body: &str) -> String {
+    let final_width = renderer.scale_width + (2 * renderer.border);
+    let final_height = renderer.scale_height + (2 * renderer.border);
+    let shape_rendering = if crisp_edges {
+        r#" shape-rendering="crispEdges""#
+    } else {
+        ""
+    };
+
+    let mut svg = String::new();
+
+    // Writing to a String can not fail
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{final_width}" height="{final_height}" viewBox="0 0 {final_width} {final_height}"{shape_rendering}>"#
+    );
+    if let Some(shape) = renderer.mask.svg_shape(final_width, final_height) {
+        let _ = write!(
+            svg,
+            r#"<defs><clipPath id="identicon-mask" shape-rendering="geometricPrecision">{shape}</clipPath></defs><g clip-path="url(#identicon-mask)">"#
+        );
+    }
+    let _ = write!(
+        svg,
+        r#"<rect width="{final_width}" height="{final_height}"{}/>"#,
+        fill(background_color)
+    );
+    svg.push_str(body);
+    if renderer.mask != Mask::None {
+        svg.push_str("</g>");
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Formats the fill attributes of a color, only including the opacity when it is not opaque.
+fn fill(color: RGBA) -> String {
+    let hex = RGB::from(color).to_hex();
+    if color.is_opaque() {
+        format!(r#" fill="{hex}""#)
+    } else {
+        let opacity = color.alpha as f32 / u8::MAX as f32;
+        format!(r#" fill="{hex}" fill-opacity="{opacity}""#)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::{CellStyle, Direction};
+    use crate::geometric;
+    use crate::mask::Mask;
+    use crate::renderer::Renderer;
+    use crate::svg::{generate_geometric_svg, generate_svg};
+
+    fn renderer(grid: (u32, u32), scale: (u32, u32), border: u32) -> Renderer {
+        Renderer {
+            grid_width: grid.0,
+            grid_height: grid.1,
+            scale_width: scale.0,
+            scale_height: scale.1,
+            border,
+            ..Renderer::default()
+        }
+    }
+
+    #[test]
+    fn svg_contains_one_rect_per_active_cell() {
+        let grid = vec![1, 0, 0, 1];
+        let svg = generate_svg(
+            &renderer((2, 2), (100, 100), 10),
+            &grid,
+            &[Direction::Up; 4],
+            &[(1, 2, 3, 255).into()],
+            (255, 255, 255, 0).into(),
+        );
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(r#"shape-rendering="crispEdges""#));
+        assert!(svg.contains(r##"fill="#ffffff" fill-opacity="0""##));
+        assert!(svg.contains(r##"fill="#010203""##));
+        assert!(svg.contains(r#"<rect x="10" y="10" width="50" height="50"/>"#));
+        assert!(svg.contains(r#"<rect x="60" y="60" width="50" height="50"/>"#));
+        assert_eq!(3, svg.matches("<rect").count());
+    }
+
+    #[test]
+    fn svg_supports_rectangular_grids() {
+        let grid = vec![0, 0, 1, 1, 0, 0];
+        let svg = generate_svg(
+            &renderer((3, 2), (300, 100), 0),
+            &grid,
+            &[Direction::Up; 6],
+            &[(1, 2, 3, 255).into()],
+            (255, 255, 255, 255).into(),
+        );
+
+        assert!(svg.contains(r#"width="300" height="100" viewBox="0 0 300 100""#));
+        assert!(svg.contains(r#"<rect x="200" y="0" width="100" height="50"/>"#));
+        assert!(svg.contains(r#"<rect x="0" y="50" width="100" height="50"/>"#));
+    }
+
+    #[test]
+    fn svg_applies_mask() {
+        let grid = vec![1];
+        let svg = generate_svg(
+            &Renderer {
+                mask: Mask::Circle,
+                ..renderer((1, 1), (10, 10), 5)
+            },
+            &grid,
+            &[Direction::Up],
+            &[(1, 2, 3, 255).into()],
+            (255, 255, 255, 255).into(),
+        );
+
+        assert!(svg.contains(
+            r#"<clipPath id="identicon-mask" shape-rendering="geometricPrecision"><ellipse cx="10" cy="10" rx="10" ry="10"/></clipPath>"#
+        ));
+        assert!(svg.contains(r#"<g clip-path="url(#identicon-mask)"><rect width="20""#));
+        assert!(svg.ends_with("</g></g></svg>"));
+    }
+
+    #[test]
+    fn svg_applies_cell_style() {
+        let grid = vec![1, 0, 0, 1];
+        let svg = generate_svg(
+            &Renderer {
+                cell_style: CellStyle::Circle,
+                ..renderer((2, 2), (100, 100), 0)
+            },
+            &grid,
+            &[Direction::Up; 4],
+            &[(1, 2, 3, 255).into()],
+            (255, 255, 255, 255).into(),
+        );
+
+        assert!(!svg.contains("shape-rendering"));
+        assert!(svg.contains(r#"<ellipse cx="25" cy="25" rx="25" ry="25"/>"#));
+        assert!(svg.contains(r#"<ellipse cx="75" cy="75" rx="25" ry="25"/>"#));
+        assert_eq!(1, svg.matches("<rect").count());
+    }
+
+    #[test]
+    fn svg_draws_geometric_tiles() {
+        let renderer = renderer((5, 5), (40, 40), 10);
+        let tiles = geometric::tiles(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x10], 2);
+        let svg = generate_geometric_svg(
+            &renderer,
+            &tiles,
+            &[(1, 1, 1, 255).into(), (2, 2, 2, 255).into()],
+            (255, 255, 255, 255).into(),
+        );
+
+        assert!(!svg.contains("shape-rendering"));
+        assert!(svg.contains(r##"<g fill="#010101" fill-rule="evenodd"><path d="M20.00 10.00L30.00 10.00L20.00 20.00Z"/>"##));
+        assert!(svg.contains(r##"<g fill="#020202" fill-rule="evenodd"><path"##));
+        assert_eq!(16, svg.matches("<path").count());
+    }
+
+    #[test]
+    fn svg_groups_palette_colors() {
+        let grid = vec![1, 0, 2, 1];
+        let svg = generate_svg(
+            &renderer((2, 2), (100, 100), 0),
+            &grid,
+            &[Direction::Up; 4],
+            &[(1, 1, 1, 255).into(), (2, 2, 2, 255).into()],
+            (255, 255, 255, 255).into(),
+        );
+
+        assert!(svg.contains(
+            r##"<g fill="#010101"><rect x="0" y="0" width="50" height="50"/><rect x="50" y="50" width="50" height="50"/></g>"##
+        ));
+        assert!(
+            svg.contains(r##"<g fill="#020202"><rect x="0" y="50" width="50" height="50"/></g>"##)
+        );
+    }
+}
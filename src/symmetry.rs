@@ -0,0 +1,288 @@
+/// Symmetry of the identicon grid.
+///
+/// Symmetry is applied to the grid generated by the [`Pattern`](crate::pattern::Pattern):
+/// every cell is copied from a single source cell within its group of symmetric cells.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// No symmetry, every cell is independent.
+    None,
+
+    /// Mirrored along the vertical (y) axis, so the left and right halves match.
+    ///
+    /// The columns right of the middle column copy the columns on the left, as identicons have
+    /// always been mirrored.
+    /// On grids with an even width this leaves the column right of the centre independent, see
+    /// [`Symmetry::VerticalCentred`] for an exact mirror.
+    ///
+    /// This is the default.
+    #[default]
+    Vertical,
+
+    /// Mirrored along the vertical (y) axis through the centre of the grid, so the left and right
+    /// halves match on grids with an even width too.
+    ///
+    /// On grids with an odd width this is the same as [`Symmetry::Vertical`].
+    VerticalCentred,
+
+    /// Mirrored along the horizontal (x) axis, so the top and bottom halves match.
+    Horizontal,
+
+    /// Mirrored along both axes, so all four quadrants match.
+    Both,
+
+    /// Mirrored along the diagonal from the top left to the bottom right corner.
+    ///
+    /// On rectangular grids, the cells whose mirrored cell is outside of the grid are independent.
+    Diagonal,
+
+    /// 2-fold rotational symmetry, so the grid looks the same when rotated by 180 degrees.
+    Rotational2,
+
+    /// 4-fold rotational symmetry, so the grid looks the same when rotated by 90 degrees.
+    ///
+    /// Rectangular grids can not be rotated by 90 degrees, so they use [`Symmetry::Rotational2`].
+    Rotational4,
+}
+
+impl Symmetry {
+    /// Gets the transforms mapping a cell onto its symmetric cells in a `width` x `height` grid.
+    pub(crate) fn transforms(&self, width: u32, height: u32) -> &'static [Transform] {
+        match self {
+            Symmetry::None => &[Transform::Identity],
+            Symmetry::Vertical | Symmetry::VerticalCentred => {
+                &[Transform::Identity, Transform::FlipX]
+            }
+            Symmetry::Horizontal => &[Transform::Identity, Transform::FlipY],
+            Symmetry::Both => &[
+                Transform::Identity,
+                Transform::FlipX,
+                Transform::FlipY,
+                Transform::Rotate180,
+            ],
+            Symmetry::Diagonal => &[Transform::Identity, Transform::Transpose],
+            Symmetry::Rotational4 if width == height => &[
+                Transform::Identity,
+                Transform::Rotate90,
+                Transform::Rotate180,
+                Transform::Rotate270,
+            ],
+            Symmetry::Rotational2 | Symmetry::Rotational4 => {
+                &[Transform::Identity, Transform::Rotate180]
+            }
+        }
+    }
+
+    /// Gets the source cell for the cell at `x`, `y` in a `width` x `height` grid, along with the
+    /// transform mapping the cell onto it.
+    ///
+    /// The source is the symmetric cell that comes first in row-major order.
+    pub(crate) fn source(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> ((u32, u32), Transform) {
+        // Keep the original mirroring, which only copies the columns right of the middle column
+        if *self == Symmetry::Vertical {
+            return if x > width / 2 {
+                ((width - 1 - x, y), Transform::FlipX)
+            } else {
+                ((x, y), Transform::Identity)
+            };
+        }
+
+        self.transforms(width, height)
+            .iter()
+            .map(|transform| (transform.apply(x, y, width, height), *transform))
+            .filter(|((x, y), _)| *x < width && *y < height)
+            .min_by_key(|((x, y), _)| (*y, *x))
+            .unwrap_or(((x, y), Transform::Identity))
+    }
+
+    /// Gets the source cell for the cell at `x`, `y` in a `width` x `height` grid.
+    pub(crate) fn source_cell(&self, x: u32, y: u32, width: u32, height: u32) -> (u32, u32) {
+        self.source(x, y, width, height).0
+    }
+}
+
+/// A transform of the grid, mapping every cell onto a symmetric cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Transform {
+    /// Keeps every cell.
+    Identity,
+    /// Mirrors along the vertical axis.
+    FlipX,
+    /// Mirrors along the horizontal axis.
+    FlipY,
+    /// Rotates by 180 degrees.
+    Rotate180,
+    /// Mirrors along the diagonal from the top left corner.
+    Transpose,
+    /// Rotates clockwise by 90 degrees.
+    Rotate90,
+    /// Rotates clockwise by 270 degrees.
+    Rotate270,
+}
+
+impl Transform {
+    /// Applies the transform to the cell at `x`, `y` in a `width` x `height` grid.
+    ///
+    /// The result may be outside of the grid for transforms that swap the axes.
+    pub(crate) fn apply(&self, x: u32, y: u32, width: u32, height: u32) -> (u32, u32) {
+        let (last_x, last_y) = (width - 1, height - 1);
+        match self {
+            Transform::Identity => (x, y),
+            Transform::FlipX => (last_x - x, y),
+            Transform::FlipY => (x, last_y - y),
+            Transform::Rotate180 => (last_x - x, last_y - y),
+            Transform::Transpose => (y, x),
+            Transform::Rotate90 => (last_y.wrapping_sub(y), x),
+            Transform::Rotate270 => (y, last_x.wrapping_sub(x)),
+        }
+    }
+
+    /// Gets the transform undoing this transform.
+    pub(crate) fn inverse(&self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            transform => *transform,
+        }
+    }
+
+    /// Applies the transform to a direction vector, where `y` points down.
+    pub(crate) fn apply_direction(&self, (dx, dy): (i32, i32)) -> (i32, i32) {
+        match self {
+            Transform::Identity => (dx, dy),
+            Transform::FlipX => (-dx, dy),
+            Transform::FlipY => (dx, -dy),
+            Transform::Rotate180 => (-dx, -dy),
+            Transform::Transpose => (dy, dx),
+            Transform::Rotate90 => (-dy, dx),
+            Transform::Rotate270 => (dy, -dx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::symmetry::Symmetry;
+
+    /// Applies the symmetry to a grid of unique cell values.
+    fn apply(symmetry: Symmetry, width: u32, height: u32) -> Vec<u32> {
+        (0..width * height)
+            .map(|location| {
+                let (x, y) =
+                    symmetry.source_cell(location % width, location / width, width, height);
+                x + y * width
+            })
+            .collect()
+    }
+
+    /// Rotates a grid by 90 degrees clockwise.
+    fn rotate(grid: &[u32], size: u32) -> Vec<u32> {
+        (0..size.pow(2))
+            .map(|location| {
+                let (x, y) = (location % size, location / size);
+                grid[(y + (size - 1 - x) * size) as usize]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vertical_works() {
+        // The column right of the centre of even widths is independent, as it has always been
+        assert_eq!(
+            vec![0, 1, 2, 0, 4, 5, 6, 4],
+            apply(Symmetry::Vertical, 4, 4)[..8]
+        );
+        assert_eq!(vec![0, 1, 2, 3, 1, 0], apply(Symmetry::Vertical, 6, 6)[..6]);
+        assert_eq!(vec![0, 1, 2, 1, 0], apply(Symmetry::Vertical, 5, 5)[..5]);
+    }
+
+    #[test]
+    fn vertical_centred_works() {
+        assert_eq!(
+            vec![0, 1, 1, 0, 4, 5, 5, 4],
+            apply(Symmetry::VerticalCentred, 4, 4)[..8]
+        );
+        assert_eq!(
+            apply(Symmetry::Vertical, 5, 5),
+            apply(Symmetry::VerticalCentred, 5, 5)
+        );
+    }
+
+    #[test]
+    fn horizontal_works() {
+        let grid = apply(Symmetry::Horizontal, 3, 3);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 0, 1, 2], grid);
+    }
+
+    #[test]
+    fn both_works() {
+        let grid = apply(Symmetry::Both, 3, 3);
+        assert_eq!(vec![0, 1, 0, 3, 4, 3, 0, 1, 0], grid);
+    }
+
+    #[test]
+    fn diagonal_works() {
+        let grid = apply(Symmetry::Diagonal, 3, 3);
+        assert_eq!(vec![0, 1, 2, 1, 4, 5, 2, 5, 8], grid);
+    }
+
+    #[test]
+    fn rotational_works() {
+        for size in [4, 5] {
+            let grid = apply(Symmetry::Rotational4, size, size);
+            assert_eq!(grid, rotate(&grid, size));
+
+            let grid = apply(Symmetry::Rotational2, size, size);
+            assert_eq!(grid, rotate(&rotate(&grid, size), size));
+            assert_ne!(grid, rotate(&grid, size));
+        }
+    }
+
+    #[test]
+    fn rectangular_grids_work() {
+        let grid = apply(Symmetry::Both, 4, 3);
+        assert_eq!(vec![0, 1, 1, 0, 4, 5, 5, 4, 0, 1, 1, 0], grid);
+
+        let grid = apply(Symmetry::Diagonal, 3, 2);
+        assert_eq!(vec![0, 1, 2, 1, 4, 5], grid);
+
+        assert_eq!(
+            apply(Symmetry::Rotational2, 4, 2),
+            apply(Symmetry::Rotational4, 4, 2)
+        );
+        assert_eq!(
+            vec![0, 1, 2, 3, 3, 2, 1, 0],
+            apply(Symmetry::Rotational4, 4, 2)
+        );
+    }
+
+    #[test]
+    fn transforms_map_directions() {
+        for symmetry in [Symmetry::Both, Symmetry::Diagonal, Symmetry::Rotational4] {
+            for location in 0..25 {
+                let (x, y) = (location % 5, location / 5);
+                let ((source_x, source_y), transform) = symmetry.source(x, y, 5, 5);
+
+                // The direction to the centre of the grid is kept by every transform
+                let to_centre = |x: u32, y: u32| ((2 - x as i32).signum(), (2 - y as i32).signum());
+                assert_eq!(
+                    to_centre(x, y),
+                    transform
+                        .inverse()
+                        .apply_direction(to_centre(source_x, source_y))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn none_works() {
+        assert_eq!((0..9).collect::<Vec<_>>(), apply(Symmetry::None, 3, 3));
+    }
+}
@@ -12,4 +12,8 @@ pub enum ThemeError {
     /// Theme failed to generate a color
     #[error("theme processing failed: {0}")]
     ThemeProcessingError(String),
+
+    /// Theme could not reach the minimum contrast against the background
+    #[error("theme contrast failed: {0}")]
+    ThemeContrastError(String),
 }
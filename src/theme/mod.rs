@@ -2,7 +2,11 @@ use std::sync::Arc;
 
 use error::ThemeError;
 
-use crate::{color::RGB, map_values::map_values};
+use crate::{
+    color::{ContrastMetric, Oklch, RGB, RGBA, hsl_to_rgb_components},
+    grid,
+    map_values::map_values,
+};
 
 /// Theme Errors
 ///
@@ -16,6 +20,30 @@ pub trait Theme {
 
     /// This should return the background color within the identicon image
     fn background_color(&self, hash: &[u8]) -> Result<RGB, ThemeError>;
+
+    /// This should return the main color within the identicon image, including its alpha
+    ///
+    /// The default implementation returns [Theme::main_color] as a fully opaque color.
+    fn main_color_rgba(&self, hash: &[u8]) -> Result<RGBA, ThemeError> {
+        self.main_color(hash).map(RGBA::from)
+    }
+
+    /// This should return the background color within the identicon image, including its alpha
+    ///
+    /// The default implementation returns [Theme::background_color] as a fully opaque color.
+    fn background_color_rgba(&self, hash: &[u8]) -> Result<RGBA, ThemeError> {
+        self.background_color(hash).map(RGBA::from)
+    }
+
+    /// This should return the colors of the active cells, starting with the main color
+    ///
+    /// Patterns choose one of these colors for every active cell with
+    /// [Pattern::palette_grid](crate::pattern::Pattern::palette_grid).
+    ///
+    /// The default implementation returns only [Theme::main_color_rgba].
+    fn palette(&self, hash: &[u8]) -> Result<Vec<RGBA>, ThemeError> {
+        Ok(vec![self.main_color_rgba(hash)?])
+    }
 }
 
 /// Simple selection theme struct
@@ -25,6 +53,13 @@ pub trait Theme {
 /// Both the main and background colors are defined as a `Vec<RGB>`.
 ///
 /// Implements [Theme]
+///
+/// With the `serde` feature, deserialization is validated the same way as [Selection::new].
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SelectionData")
+)]
 pub struct Selection {
     /// A vector of input colors to choose from based on the input hash.
     /// This can be a vector of one value to allow for constant image colors.
@@ -60,6 +95,23 @@ impl Selection {
     }
 }
 
+/// Unvalidated [Selection] fields, used for deserialization
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SelectionData {
+    main: Vec<RGB>,
+    background: Vec<RGB>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SelectionData> for Selection {
+    type Error = ThemeError;
+
+    fn try_from(value: SelectionData) -> Result<Self, Self::Error> {
+        Selection::new(value.main, value.background)
+    }
+}
+
 impl Theme for Selection {
     fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
         if self.main.is_empty() {
@@ -88,9 +140,24 @@ impl Theme for Selection {
 ///
 /// This will generate a main color within the defined HSL Range.
 ///
-/// The background color is based on a predefined `Vec<RGB>` and the color is selected by the hash value.
+/// A hue range with `hue_max` smaller than `hue_min` wraps around 360 degrees, e.g. 330.0 to 30.0
+/// for reds only.
+/// Further disjoint hue bands can be added with [HSLRange::add_hue_band].
+///
+/// The background color is selected from a predefined `Vec<RGB>` by the hash value, or derived
+/// from the hash or the main color, see [HSLRange::set_derived_background].
+///
+/// The palette holds the main color followed by further colors within the same HSL Range, see
+/// [HSLRange::set_palette_size].
 ///
 /// Implements [Theme]
+///
+/// With the `serde` feature, deserialization is validated the same way as [HSLRange::new].
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HSLRangeData")
+)]
 pub struct HSLRange {
     /// The minimum hue
     /// A value between 0.0 and 360.0
@@ -100,6 +167,10 @@ pub struct HSLRange {
     /// A value between 0.0 and 360.0
     hue_max: f32,
 
+    /// Further hue bands after the band from hue_min to hue_max, as (min, max) pairs.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    hue_bands: Vec<(f32, f32)>,
+
     /// The minimum saturation
     /// A value between 0.0 and 100.0 as a percent.
     /// e.g. 75.0 will become 0.750 in a HSL calculation.
@@ -124,6 +195,13 @@ pub struct HSLRange {
     /// This can be a vector of one value to allow for constant backgrounds.
     // background: Vec<RGB>,
     background: Vec<RGB>,
+
+    /// The derived background color, used instead of the background vector when set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    derived_background: Option<DerivedBackground>,
+
+    /// The number of palette colors, including the main color.
+    palette_size: usize,
 }
 
 impl HSLRange {
@@ -131,6 +209,7 @@ impl HSLRange {
     ///
     /// `hue_min` and `hue_max` are the range of possible hue values.
     /// They are expected to be between 0.0 and 360.0
+    /// When `hue_max` is smaller than `hue_min`, the range wraps around 360 degrees.
     ///
     /// `saturation_min` and `saturation_max` are the range of possible saturation values.
     /// They are expected to be between 0.0 and 100.0
@@ -151,20 +230,176 @@ impl HSLRange {
         let theme = HSLRange {
             hue_min,
             hue_max,
+            hue_bands: Vec::new(),
             saturation_min,
             saturation_max,
             lightness_min,
             lightness_max,
             background,
+            derived_background: None,
+            palette_size: 1,
         };
 
         theme.validate().map(|_| theme)
     }
 
+    /// Sets the number of palette colors, including the main color, e.g. 3 for three-tone
+    /// identicons with a multi-color pattern.
+    ///
+    /// The colors after the main color are generated from the SHAKE256 extension of the hash.
+    /// At least the main color is always returned.
+    ///
+    /// Default is 1
+    pub fn set_palette_size(&mut self, palette_size: usize) -> &mut Self {
+        self.palette_size = palette_size;
+        self
+    }
+
+    /// Sets a background color derived from the hash or the main color, e.g. for fully colored
+    /// identicons.
+    ///
+    /// When set, the background vector is not used and may be empty.
+    /// `None` selects from the background vector again.
+    ///
+    /// Default is None
+    pub fn set_derived_background(
+        &mut self,
+        derived_background: Option<DerivedBackground>,
+    ) -> &mut Self {
+        self.derived_background = derived_background;
+        self
+    }
+
+    /// Adds a further band of possible hue values, e.g. to pick from a few brand hues.
+    ///
+    /// The hash is mapped uniformly over the combined length of all bands, starting with the band
+    /// from `hue_min` to `hue_max`, so wider bands are picked more often.
+    /// Like the first band, `hue_max` smaller than `hue_min` wraps around 360 degrees.
+    pub fn add_hue_band(&mut self, hue_min: f32, hue_max: f32) -> &mut Self {
+        self.hue_bands.push((hue_min, hue_max));
+        self
+    }
+
+    /// Generates a color within the HSL Range from the first 4 bytes of the hash, repeating them
+    /// when the hash is shorter.
+    fn color(&self, hash: &[u8]) -> RGB {
+        let (hue, saturation, lightness) = self.hsl(hash);
+        hsl_color(hue, saturation, lightness)
+    }
+
+    /// Generates the hue, saturation and lightness of [HSLRange::color], with the saturation and
+    /// lightness between 0.0 and 1.0.
+    fn hsl(&self, hash: &[u8]) -> (f32, f32, f32) {
+        // Compute hash for hue space in larger bitspace
+        let hue_hash = ((hash[0 % hash.len()] as u16) << 8) | hash[1 % hash.len()] as u16;
+
+        // Compute HSL values
+        let hue = if self.hue_bands.is_empty() && self.hue_min <= self.hue_max {
+            let hash_hue = map_values(
+                hue_hash as f32,
+                u16::MIN as f32,
+                u16::MAX as f32,
+                self.hue_min,
+                self.hue_max,
+            );
+
+            // Handle 0 degree hue is equivalent to 360 degree hue
+            hash_hue % 360.0
+        } else {
+            self.banded_hue(hue_hash)
+        };
+
+        // Saturation should be between 0.5 and 0.75 for pastel colors
+        let saturation = map_values(
+            hash[2 % hash.len()] as f32,
+            u8::MIN as f32,
+            u8::MAX as f32,
+            self.saturation_min,
+            self.saturation_max,
+        ) / 100.0;
+
+        // Lightness should be between 0.6 and 0.70 for pastel colors
+        let lightness = map_values(
+            hash[3 % hash.len()] as f32,
+            u8::MIN as f32,
+            u8::MAX as f32,
+            self.lightness_min,
+            self.lightness_max,
+        ) / 100.0;
+
+        (hue, saturation, lightness)
+    }
+
+    /// Returns all hue bands as (min, max) pairs, starting with the band from hue_min to hue_max.
+    fn all_hue_bands(&self) -> impl Iterator<Item = (f32, f32)> + Clone + '_ {
+        std::iter::once((self.hue_min, self.hue_max)).chain(self.hue_bands.iter().copied())
+    }
+
+    /// Maps the hue hash uniformly over the combined length of all hue bands.
+    fn banded_hue(&self, hue_hash: u16) -> f32 {
+        banded_hue(hue_hash, self.all_hue_bands())
+    }
+
+    /// Generates the derived background color, see [DerivedBackground].
+    fn derived_background_color(&self, derived: &DerivedBackground, hash: &[u8]) -> RGB {
+        match *derived {
+            DerivedBackground::Range {
+                hue_min,
+                hue_max,
+                saturation_min,
+                saturation_max,
+                lightness_min,
+                lightness_max,
+            } => {
+                let byte = |index: usize| hash[index % hash.len()] as f32;
+                let hue_hash = ((hash[4 % hash.len()] as u16) << 8) | hash[5 % hash.len()] as u16;
+                let saturation =
+                    map_values(byte(6), 0.0, 255.0, saturation_min, saturation_max) / 100.0;
+                let lightness =
+                    map_values(byte(7), 0.0, 255.0, lightness_min, lightness_max) / 100.0;
+                hsl_color(
+                    banded_hue(hue_hash, std::iter::once((hue_min, hue_max))),
+                    saturation,
+                    lightness,
+                )
+            }
+            DerivedBackground::Complementary {
+                saturation,
+                lightness,
+            } => {
+                let (hue, _, _) = self.hsl(hash);
+                hsl_color(
+                    (hue + 180.0).rem_euclid(360.0),
+                    saturation / 100.0,
+                    lightness / 100.0,
+                )
+            }
+            DerivedBackground::Analogous {
+                hue_offset,
+                saturation,
+                lightness,
+            } => {
+                let (hue, _, _) = self.hsl(hash);
+                hsl_color(
+                    (hue + hue_offset).rem_euclid(360.0),
+                    saturation / 100.0,
+                    lightness / 100.0,
+                )
+            }
+            DerivedBackground::Tint { lightness } => {
+                let (hue, saturation, _) = self.hsl(hash);
+                hsl_color(hue, saturation, lightness / 100.0)
+            }
+        }
+    }
+
     fn validate(&self) -> Result<(), ThemeError> {
-        if self.hue_max < self.hue_min {
+        let wraps_out_of_range = self.all_hue_bands().any(|(min, max)| {
+            max < min && !((0.0..=360.0).contains(&min) && (0.0..=360.0).contains(&max))
+        });
+        if wraps_out_of_range {
             Err(ThemeError::ThemeValidationError(
-                "hue_max must be larger than hue_min".to_string(),
+                "hue ranges wrapping around 360 must be between 0 and 360".to_string(),
             ))
         } else if self.saturation_max < self.saturation_min {
             Err(ThemeError::ThemeValidationError(
@@ -174,42 +409,350 @@ impl HSLRange {
             Err(ThemeError::ThemeValidationError(
                 "lightness_max must be larger than lightness_min".to_string(),
             ))
+        } else if let Some(derived) = &self.derived_background {
+            derived.validate()
         } else {
             Ok(())
         }
     }
 }
 
+/// Derived background colors of an [HSLRange]
+///
+/// Saturation and lightness values are between 0.0 and 100.0 as a percent, like the [HSLRange]
+/// fields, and hues are in degrees.
+///
+/// Set with [HSLRange::set_derived_background].
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DerivedBackground {
+    /// A color within its own HSL range, generated from bytes 4 to 7 of the hash.
+    ///
+    /// A `hue_max` smaller than `hue_min` wraps around 360 degrees.
+    Range {
+        /// The minimum hue
+        hue_min: f32,
+        /// The maximum hue
+        hue_max: f32,
+        /// The minimum saturation
+        saturation_min: f32,
+        /// The maximum saturation
+        saturation_max: f32,
+        /// The minimum lightness
+        lightness_min: f32,
+        /// The maximum lightness
+        lightness_max: f32,
+    },
+
+    /// The complementary color, with the hue of the main color turned by 180 degrees.
+    Complementary {
+        /// The saturation of the background
+        saturation: f32,
+        /// The lightness of the background
+        lightness: f32,
+    },
+
+    /// An analogous color, with the hue of the main color turned by `hue_offset` degrees, usually
+    /// 30.0 or -30.0.
+    Analogous {
+        /// The degrees added to the hue of the main color
+        hue_offset: f32,
+        /// The saturation of the background
+        saturation: f32,
+        /// The lightness of the background
+        lightness: f32,
+    },
+
+    /// A lighter tint or darker shade of the main color, with its hue and saturation.
+    Tint {
+        /// The lightness of the background
+        lightness: f32,
+    },
+}
+
+impl DerivedBackground {
+    fn validate(&self) -> Result<(), ThemeError> {
+        match *self {
+            DerivedBackground::Range {
+                hue_min,
+                hue_max,
+                saturation_min,
+                saturation_max,
+                lightness_min,
+                lightness_max,
+            } => {
+                if hue_max < hue_min
+                    && !((0.0..=360.0).contains(&hue_min) && (0.0..=360.0).contains(&hue_max))
+                {
+                    Err(ThemeError::ThemeValidationError(
+                        "background hue ranges wrapping around 360 must be between 0 and 360"
+                            .to_string(),
+                    ))
+                } else if saturation_max < saturation_min {
+                    Err(ThemeError::ThemeValidationError(
+                        "background saturation_max must be larger than saturation_min".to_string(),
+                    ))
+                } else if lightness_max < lightness_min {
+                    Err(ThemeError::ThemeValidationError(
+                        "background lightness_max must be larger than lightness_min".to_string(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            DerivedBackground::Complementary { .. }
+            | DerivedBackground::Analogous { .. }
+            | DerivedBackground::Tint { .. } => Ok(()),
+        }
+    }
+}
+
+/// Maps the hue hash uniformly over the combined length of the hue bands, where bands with a max
+/// smaller than their min wrap around 360 degrees.
+fn banded_hue(hue_hash: u16, bands: impl Iterator<Item = (f32, f32)> + Clone) -> f32 {
+    let length = |(min, max): (f32, f32)| {
+        if max < min {
+            max + 360.0 - min
+        } else {
+            max - min
+        }
+    };
+    let total: f32 = bands.clone().map(length).sum();
+
+    let mut position = map_values(
+        hue_hash as f32,
+        u16::MIN as f32,
+        u16::MAX as f32,
+        0.0,
+        total,
+    );
+    let mut hue = 0.0;
+    for band in bands {
+        // Rounding can leave the position just past the last band, which then ends at its max
+        hue = band.0 + position.min(length(band));
+        if position <= length(band) {
+            break;
+        }
+        position -= length(band);
+    }
+
+    hue.rem_euclid(360.0)
+}
+
+/// Converts HSL values to RGB, with the saturation and lightness between 0.0 and 1.0.
+fn hsl_color(hue: f32, saturation: f32, lightness: f32) -> RGB {
+    let (red, green, blue) = hsl_to_rgb_components(hue, saturation, lightness);
+    let red = red * 255.0;
+    let green = green * 255.0;
+    let blue = blue * 255.0;
+
+    RGB {
+        red: red as u8,
+        green: green as u8,
+        blue: blue as u8,
+    }
+}
+
+/// Unvalidated [HSLRange] fields, used for deserialization
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct HSLRangeData {
+    hue_min: f32,
+    hue_max: f32,
+    #[serde(default)]
+    hue_bands: Vec<(f32, f32)>,
+    saturation_min: f32,
+    saturation_max: f32,
+    lightness_min: f32,
+    lightness_max: f32,
+    background: Vec<RGB>,
+    #[serde(default)]
+    derived_background: Option<DerivedBackground>,
+    #[serde(default = "default_palette_size")]
+    palette_size: usize,
+}
+
+/// The palette size of themes without a palette.
+#[cfg(feature = "serde")]
+fn default_palette_size() -> usize {
+    1
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HSLRangeData> for HSLRange {
+    type Error = ThemeError;
+
+    fn try_from(value: HSLRangeData) -> Result<Self, Self::Error> {
+        let mut theme = HSLRange::new(
+            value.hue_min,
+            value.hue_max,
+            value.saturation_min,
+            value.saturation_max,
+            value.lightness_min,
+            value.lightness_max,
+            value.background,
+        )?;
+        theme
+            .set_palette_size(value.palette_size)
+            .set_derived_background(value.derived_background);
+        for (hue_min, hue_max) in value.hue_bands {
+            theme.add_hue_band(hue_min, hue_max);
+        }
+        theme.validate().map(|_| theme)
+    }
+}
+
 impl Theme for HSLRange {
     fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
         // Validate the fields
         self.validate()?;
 
-        // Compute hash for hue space in larger bitspace
-        let hue_hash = ((hash[0 % hash.len()] as u16) << 8) | hash[1 % hash.len()] as u16;
+        Ok(self.color(hash))
+    }
 
-        // Compute HSL values
-        let hash_hue = map_values(
+    fn background_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        if let Some(derived) = &self.derived_background {
+            self.validate()?;
+            Ok(self.derived_background_color(derived, hash))
+        } else if self.background.is_empty() {
+            Err(ThemeError::ThemeValidationError(
+                "background color selection is empty".to_string(),
+            ))
+        } else {
+            let index = hash[2 % hash.len()] as usize % self.background.len();
+            Ok(self.background[index])
+        }
+    }
+
+    fn palette(&self, hash: &[u8]) -> Result<Vec<RGBA>, ThemeError> {
+        self.validate()?;
+        Ok(range_palette(hash, self.palette_size, |bytes| {
+            self.color(bytes)
+        }))
+    }
+}
+
+/// Oklch range theme struct
+///
+/// This will generate a main color within the defined Oklch range.
+/// Unlike [HSLRange], the lightness is perceptual, so every hue looks equally bright and
+/// identicons have an even contrast against the background.
+///
+/// Colors outside of the sRGB gamut have their chroma reduced until they fit, keeping their
+/// lightness and hue, see [Oklch::clamp_chroma].
+///
+/// The background color is based on a predefined `Vec<RGB>` and the color is selected by the hash value.
+///
+/// The palette holds the main color followed by further colors within the same Oklch range, see
+/// [OklchRange::set_palette_size].
+///
+/// Implements [Theme]
+///
+/// With the `serde` feature, deserialization is validated the same way as [OklchRange::new].
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "OklchRangeData")
+)]
+pub struct OklchRange {
+    /// The minimum hue
+    /// A value between 0.0 and 360.0
+    hue_min: f32,
+
+    /// The maximum hue
+    /// A value between 0.0 and 360.0
+    hue_max: f32,
+
+    /// The minimum chroma
+    /// A value from 0.0, where the most saturated sRGB colors reach about 0.37.
+    chroma_min: f32,
+
+    /// The maximum chroma
+    /// A value from 0.0, where the most saturated sRGB colors reach about 0.37.
+    chroma_max: f32,
+
+    /// The minimum lightness
+    /// A value between 0.0 and 100.0 as a percent.
+    lightness_min: f32,
+
+    /// The maximum lightness
+    /// A value between 0.0 and 100.0 as a percent.
+    lightness_max: f32,
+
+    /// A vector of background colors to choose from based on the input hash.
+    /// This can be a vector of one value to allow for constant backgrounds.
+    background: Vec<RGB>,
+
+    /// The number of palette colors, including the main color.
+    palette_size: usize,
+}
+
+impl OklchRange {
+    /// Generates a new OklchRange theme
+    ///
+    /// `hue_min` and `hue_max` are the range of possible hue values.
+    /// They are expected to be between 0.0 and 360.0
+    ///
+    /// `chroma_min` and `chroma_max` are the range of possible chroma values.
+    /// They are expected to be at least 0.0, with about 0.37 for the most saturated colors.
+    ///
+    /// `lightness_min` and `lightness_max` are the range of possible lightness values.
+    /// They are expected to be between 0.0 and 100.0
+    ///
+    /// `background` is used as the possible colors of the background.
+    pub fn new(
+        hue_min: f32,
+        hue_max: f32,
+        chroma_min: f32,
+        chroma_max: f32,
+        lightness_min: f32,
+        lightness_max: f32,
+        background: Vec<RGB>,
+    ) -> Result<OklchRange, ThemeError> {
+        let theme = OklchRange {
+            hue_min,
+            hue_max,
+            chroma_min,
+            chroma_max,
+            lightness_min,
+            lightness_max,
+            background,
+            palette_size: 1,
+        };
+
+        theme.validate().map(|_| theme)
+    }
+
+    /// Sets the number of palette colors, including the main color, e.g. 3 for three-tone
+    /// identicons with a multi-color pattern.
+    ///
+    /// The colors after the main color are generated from the SHAKE256 extension of the hash.
+    /// At least the main color is always returned.
+    ///
+    /// Default is 1
+    pub fn set_palette_size(&mut self, palette_size: usize) -> &mut Self {
+        self.palette_size = palette_size;
+        self
+    }
+
+    /// Generates a color within the Oklch range from the first 4 bytes of the hash, repeating
+    /// them when the hash is shorter.
+    fn color(&self, hash: &[u8]) -> RGB {
+        let hue_hash = ((hash[0 % hash.len()] as u16) << 8) | hash[1 % hash.len()] as u16;
+        let hue = map_values(
             hue_hash as f32,
             u16::MIN as f32,
             u16::MAX as f32,
             self.hue_min,
             self.hue_max,
         );
-
-        // Handle 0 degree hue is equivalent to 360 degree hue
-        let hue = hash_hue % 360.0;
-
-        // Saturation should be between 0.5 and 0.75 for pastel colors
-        let saturation = map_values(
+        let chroma = map_values(
             hash[2 % hash.len()] as f32,
             u8::MIN as f32,
             u8::MAX as f32,
-            self.saturation_min,
-            self.saturation_max,
-        ) / 100.0;
-
-        // Lightness should be between 0.6 and 0.70 for pastel colors
+            self.chroma_min,
+            self.chroma_max,
+        );
         let lightness = map_values(
             hash[3 % hash.len()] as f32,
             u8::MIN as f32,
@@ -218,35 +761,77 @@ impl Theme for HSLRange {
             self.lightness_max,
         ) / 100.0;
 
-        // Convert HSL to RGB
-        let chroma = (1.0 - ((2.0 * lightness) - 1.0).abs()) * saturation;
-        let hue_prime = hue / 60.0;
-        let x = chroma * (1.0 - ((hue_prime % 2.0) - 1.0).abs());
-
-        // Get Prime RGB Values
-        let (r_prime, g_prime, b_prime) = match hue_prime {
-            0.0..1.0 => (chroma, x, 0.0),
-            1.0..2.0 => (x, chroma, 0.0),
-            2.0..3.0 => (0.0, chroma, x),
-            3.0..4.0 => (0.0, x, chroma),
-            4.0..5.0 => (x, 0.0, chroma),
-            5.0..=6.0 => (chroma, 0.0, x),
-            // This should not occur as the hue is between 0 and 360, which casts down to between 0-6
-            _ => (0.0, 0.0, 0.0),
-        };
+        Oklch {
+            lightness,
+            chroma,
+            hue,
+        }
+        .into()
+    }
 
-        // Lightness modifier
-        let m = lightness - chroma * 0.5;
+    fn validate(&self) -> Result<(), ThemeError> {
+        if self.hue_max < self.hue_min {
+            Err(ThemeError::ThemeValidationError(
+                "hue_max must be larger than hue_min".to_string(),
+            ))
+        } else if self.chroma_max < self.chroma_min {
+            Err(ThemeError::ThemeValidationError(
+                "chroma_max must be larger than chroma_min".to_string(),
+            ))
+        } else if self.chroma_min < 0.0 {
+            Err(ThemeError::ThemeValidationError(
+                "chroma_min must not be negative".to_string(),
+            ))
+        } else if self.lightness_max < self.lightness_min {
+            Err(ThemeError::ThemeValidationError(
+                "lightness_max must be larger than lightness_min".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
 
-        let red = (r_prime + m) * 255.0;
-        let green = (g_prime + m) * 255.0;
-        let blue = (b_prime + m) * 255.0;
+/// Unvalidated [OklchRange] fields, used for deserialization
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct OklchRangeData {
+    hue_min: f32,
+    hue_max: f32,
+    chroma_min: f32,
+    chroma_max: f32,
+    lightness_min: f32,
+    lightness_max: f32,
+    background: Vec<RGB>,
+    #[serde(default = "default_palette_size")]
+    palette_size: usize,
+}
 
-        Ok(RGB {
-            red: red as u8,
-            green: green as u8,
-            blue: blue as u8,
-        })
+#[cfg(feature = "serde")]
+impl TryFrom<OklchRangeData> for OklchRange {
+    type Error = ThemeError;
+
+    fn try_from(value: OklchRangeData) -> Result<Self, Self::Error> {
+        let mut theme = OklchRange::new(
+            value.hue_min,
+            value.hue_max,
+            value.chroma_min,
+            value.chroma_max,
+            value.lightness_min,
+            value.lightness_max,
+            value.background,
+        )?;
+        theme.set_palette_size(value.palette_size);
+        Ok(theme)
+    }
+}
+
+impl Theme for OklchRange {
+    fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        // Validate the fields
+        self.validate()?;
+
+        Ok(self.color(hash))
     }
 
     fn background_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
@@ -259,6 +844,160 @@ impl Theme for HSLRange {
             Ok(self.background[index])
         }
     }
+
+    fn palette(&self, hash: &[u8]) -> Result<Vec<RGBA>, ThemeError> {
+        self.validate()?;
+        Ok(range_palette(hash, self.palette_size, |bytes| {
+            self.color(bytes)
+        }))
+    }
+}
+
+/// Generates the palette of a range theme with `palette_size` colors, of at least 1.
+///
+/// The main color uses the hash and every further color uses the next 4 bytes following the hash.
+fn range_palette(hash: &[u8], palette_size: usize, color: impl Fn(&[u8]) -> RGB) -> Vec<RGBA> {
+    let mut palette = vec![RGBA::from(color(hash))];
+    let extension = grid::extend_hash(hash, hash.len() + 4 * palette_size.max(1));
+    palette.extend(
+        extension[hash.len()..]
+            .chunks_exact(4)
+            .take(palette_size.saturating_sub(1))
+            .map(|bytes| RGBA::from(color(bytes))),
+    );
+    palette
+}
+
+/// The lightness step used by [MinimumContrast] to adjust colors.
+const CONTRAST_LIGHTNESS_STEP: f32 = 0.005;
+
+/// Minimum contrast theme struct
+///
+/// This wraps another theme and adjusts its main color and palette until every color reaches a
+/// minimum contrast against the background, measured with a [ContrastMetric].
+///
+/// Colors with enough contrast are unchanged.
+/// Other colors have their Oklch lightness moved in small steps away from the background, keeping
+/// their hue, until the minimum is reached.
+/// The direction with more contrast at its end, black or white, is tried first.
+/// The adjustment only depends on the colors, so the same hash always gives the same colors.
+///
+/// The background and the alpha of every color are passed through unchanged, and the contrast is
+/// measured between the opaque colors.
+///
+/// Generating a color fails with [ThemeError::ThemeContrastError] if neither black nor white reach
+/// the minimum on the background.
+///
+/// Implements [Theme]
+pub struct MinimumContrast {
+    /// The theme whose colors are adjusted.
+    theme: Arc<dyn Theme + Send + Sync>,
+
+    /// The metric used to measure the contrast.
+    metric: ContrastMetric,
+
+    /// The minimum contrast in the units of the metric.
+    minimum: f32,
+}
+
+impl MinimumContrast {
+    /// Generates a new MinimumContrast theme wrapping `theme`
+    ///
+    /// `minimum` is the minimum contrast of every color against the background, in the units of
+    /// the `metric`.
+    /// It is expected to be between 0.0 and [ContrastMetric::max_contrast].
+    pub fn new(
+        theme: Arc<dyn Theme + Send + Sync>,
+        metric: ContrastMetric,
+        minimum: f32,
+    ) -> Result<MinimumContrast, ThemeError> {
+        if !(0.0..=metric.max_contrast()).contains(&minimum) {
+            return Err(ThemeError::ThemeValidationError(format!(
+                "minimum contrast must be between 0 and {}",
+                metric.max_contrast()
+            )));
+        }
+
+        Ok(MinimumContrast {
+            theme,
+            metric,
+            minimum,
+        })
+    }
+
+    /// Adjusts the lightness of `color` until it reaches the minimum contrast on `background`.
+    fn adjust(&self, color: RGB, background: RGB) -> Result<RGB, ThemeError> {
+        if self.metric.contrast(color, background) >= self.minimum {
+            return Ok(color);
+        }
+
+        let (black, white) = (RGB::from((0, 0, 0)), RGB::from((255, 255, 255)));
+        let mut targets = [0.0, 1.0];
+        if self.metric.contrast(white, background) > self.metric.contrast(black, background) {
+            targets.reverse();
+        }
+
+        let start = Oklch::from(color);
+        for target in targets {
+            let steps = ((target - start.lightness).abs() / CONTRAST_LIGHTNESS_STEP).ceil() as u32;
+            for step in 1..=steps {
+                let lightness = if target > start.lightness {
+                    (start.lightness + step as f32 * CONTRAST_LIGHTNESS_STEP).min(target)
+                } else {
+                    (start.lightness - step as f32 * CONTRAST_LIGHTNESS_STEP).max(target)
+                };
+                let adjusted = RGB::from(Oklch { lightness, ..start });
+                if self.metric.contrast(adjusted, background) >= self.minimum {
+                    return Ok(adjusted);
+                }
+            }
+        }
+
+        Err(ThemeError::ThemeContrastError(format!(
+            "no color reaches a contrast of {} on {background}",
+            self.minimum
+        )))
+    }
+
+    /// Adjusts an RGBA color, keeping its alpha.
+    fn adjust_rgba(&self, color: RGBA, background: RGB) -> Result<RGBA, ThemeError> {
+        let RGB { red, green, blue } = self.adjust(color.into(), background)?;
+        Ok(RGBA {
+            red,
+            green,
+            blue,
+            alpha: color.alpha,
+        })
+    }
+}
+
+impl Theme for MinimumContrast {
+    fn main_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        let background = self.theme.background_color(hash)?;
+        self.adjust(self.theme.main_color(hash)?, background)
+    }
+
+    fn background_color(&self, hash: &[u8]) -> Result<RGB, ThemeError> {
+        self.theme.background_color(hash)
+    }
+
+    fn main_color_rgba(&self, hash: &[u8]) -> Result<RGBA, ThemeError> {
+        let background = self.theme.background_color(hash)?;
+        self.adjust_rgba(self.theme.main_color_rgba(hash)?, background)
+    }
+
+    fn background_color_rgba(&self, hash: &[u8]) -> Result<RGBA, ThemeError> {
+        self.theme.background_color_rgba(hash)
+    }
+
+    fn palette(&self, hash: &[u8]) -> Result<Vec<RGBA>, ThemeError> {
+        let background = self.theme.background_color(hash)?;
+        self.theme
+            .palette(hash)?
+            .into_iter()
+            .map(|color| self.adjust_rgba(color, background))
+            .collect()
+    }
 }
 
 /// The default theme
@@ -269,6 +1008,7 @@ pub fn default_theme() -> Arc<dyn Theme + Send + Sync> {
     Arc::new(HSLRange {
         hue_min: 0.0,
         hue_max: 360.0,
+        hue_bands: Vec::new(),
         saturation_min: 50.0,
         saturation_max: 75.0,
         lightness_min: 60.0,
@@ -278,6 +1018,35 @@ pub fn default_theme() -> Arc<dyn Theme + Send + Sync> {
             green: 240,
             blue: 240,
         }],
+        derived_background: None,
+        palette_size: 1,
+    })
+}
+
+/// The Oklch theme
+///
+/// This is a muted pastel theme like [default_theme], with a perceptually even lightness.
+pub fn oklch_theme() -> Arc<dyn Theme + Send + Sync> {
+    oklch_palette_theme(1)
+}
+
+/// The Oklch theme with a palette
+///
+/// This is the [oklch_theme] with `palette_size` colors, see [OklchRange::set_palette_size].
+pub fn oklch_palette_theme(palette_size: usize) -> Arc<dyn Theme + Send + Sync> {
+    Arc::new(OklchRange {
+        hue_min: 0.0,
+        hue_max: 360.0,
+        chroma_min: 0.08,
+        chroma_max: 0.14,
+        lightness_min: 72.0,
+        lightness_max: 80.0,
+        background: vec![RGB {
+            red: 240,
+            green: 240,
+            blue: 240,
+        }],
+        palette_size,
     })
 }
 
@@ -341,9 +1110,15 @@ pub fn pastel_selection_theme() -> Arc<dyn Theme + Send + Sync> {
 mod tests {
     use std::sync::Arc;
 
-    use crate::{color::RGB, hash};
+    use crate::{
+        color::{ContrastMetric, Oklch, RGB, RGBA},
+        hash,
+    };
 
-    use super::{HSLRange, Selection, Theme, default_theme, pastel_selection_theme};
+    use super::{
+        DerivedBackground, HSLRange, MinimumContrast, OklchRange, Selection, Theme, default_theme,
+        error::ThemeError, hsl_color, oklch_palette_theme, oklch_theme, pastel_selection_theme,
+    };
     const CONSISTENCY_STRING_1: &str = "TEST CONSISTENCY";
     const CONSISTENCY_STRING_2: &str = "TEST CONSISTENCY ALTERNATE";
     const CONSISTENCY_STRING_3: &str = "CONSISTENCY TEST INPUT";
@@ -395,11 +1170,14 @@ mod tests {
         let theme = Arc::new(HSLRange {
             hue_min: 0.0,
             hue_max: 100.0,
+            hue_bands: Vec::new(),
             saturation_min: 0.0,
             saturation_max: 100.0,
             lightness_min: 0.0,
             lightness_max: 100.0,
             background: vec![(0, 0, 0).into(), (255, 255, 255).into()],
+            derived_background: None,
+            palette_size: 1,
         });
 
         let expected_main_color: RGB = (67, 77, 16).into();
@@ -423,6 +1201,338 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hsl_range_palette_works() {
+        let hash = hash::hash_value(CONSISTENCY_STRING_1);
+        let mut theme = HSLRange::new(0.0, 360.0, 50.0, 75.0, 60.0, 70.0, vec![(0, 0, 0).into()])
+            .expect("theme should be valid");
+
+        let palette = theme.palette(&hash).expect("could not generate palette");
+        assert_eq!(vec![RGBA::from((116, 93, 222, 255))], palette);
+
+        theme.set_palette_size(3);
+        let palette = theme.palette(&hash).expect("could not generate palette");
+        assert_eq!(3, palette.len());
+        assert_eq!(RGBA::from((116, 93, 222, 255)), palette[0]);
+        assert_ne!(palette[0], palette[1]);
+        assert_ne!(palette[1], palette[2]);
+        assert_eq!(
+            palette,
+            theme.palette(&hash).expect("could not generate palette")
+        );
+
+        // There is always at least the main color
+        theme.set_palette_size(0);
+        assert_eq!(
+            1,
+            theme
+                .palette(&hash)
+                .expect("could not generate palette")
+                .len()
+        );
+    }
+
+    #[test]
+    fn default_palette_is_main_color() {
+        let hash = hash::hash_value(CONSISTENCY_STRING_1);
+        let theme = pastel_selection_theme();
+        let palette = theme.palette(&hash).expect("could not generate palette");
+        assert_eq!(
+            vec![RGBA::from(
+                theme
+                    .main_color(&hash)
+                    .expect("could not generate main color")
+            )],
+            palette
+        );
+    }
+
+    #[test]
+    fn oklch_range_has_even_lightness() {
+        let theme = oklch_theme();
+        let lightness: Vec<f32> = [
+            CONSISTENCY_STRING_1,
+            CONSISTENCY_STRING_2,
+            CONSISTENCY_STRING_3,
+        ]
+        .iter()
+        .map(|input| {
+            let color = theme
+                .main_color(&hash::hash_value(input))
+                .expect("could not generate main color");
+            Oklch::from(color).lightness
+        })
+        .collect();
+
+        // The lightness stays within the range, up to rounding to 8 bit components
+        for value in lightness {
+            assert!((0.715..=0.805).contains(&value), "lightness {value}");
+        }
+    }
+
+    #[test]
+    fn oklch_palette_theme_extends_oklch_theme() {
+        let hash = hash::hash_value(CONSISTENCY_STRING_1);
+        let palette = oklch_palette_theme(3)
+            .palette(&hash)
+            .expect("could not generate palette");
+        assert_eq!(3, palette.len());
+        assert_eq!(
+            oklch_theme()
+                .main_color_rgba(&hash)
+                .expect("could not generate main color"),
+            palette[0]
+        );
+    }
+
+    #[test]
+    fn oklch_range_clamps_chroma() {
+        let mut theme = OklchRange::new(0.0, 360.0, 0.4, 0.4, 50.0, 50.0, vec![(0, 0, 0).into()])
+            .expect("theme should be valid");
+        theme.set_palette_size(8);
+
+        let palette = theme
+            .palette(&hash::hash_value(CONSISTENCY_STRING_1))
+            .expect("could not generate palette");
+        assert_eq!(8, palette.len());
+        for color in palette {
+            let color = Oklch::from(RGB::from(color));
+            assert!((color.lightness - 0.5).abs() < 0.01);
+            assert!(color.chroma < 0.4);
+        }
+    }
+
+    #[test]
+    fn oklch_theme_validation() {
+        let background = vec![(255, 255, 255).into()];
+        assert!(OklchRange::new(0.0, 360.0, 0.0, 0.2, 0.0, 100.0, background.clone()).is_ok());
+        assert!(OklchRange::new(360.0, 0.0, 0.0, 0.2, 0.0, 100.0, background.clone()).is_err());
+        assert!(OklchRange::new(0.0, 360.0, 0.2, 0.0, 0.0, 100.0, background.clone()).is_err());
+        assert!(OklchRange::new(0.0, 360.0, -0.1, 0.2, 0.0, 100.0, background.clone()).is_err());
+        assert!(OklchRange::new(0.0, 360.0, 0.0, 0.2, 100.0, 0.0, background).is_err());
+
+        let theme = OklchRange::new(0.0, 360.0, 0.0, 0.2, 0.0, 100.0, vec![])
+            .expect("theme should be valid");
+        assert!(
+            theme
+                .background_color(&hash::hash_value(CONSISTENCY_STRING_1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn minimum_contrast_reaches_minimum() {
+        // Light pastels that are hard to see on the default background
+        let background: RGB = (240, 240, 240).into();
+        let mut pastel = HSLRange::new(0.0, 360.0, 80.0, 100.0, 85.0, 90.0, vec![background])
+            .expect("theme should be valid");
+        pastel.set_palette_size(3);
+        let pastel = Arc::new(pastel);
+
+        for (metric, minimum) in [(ContrastMetric::Wcag2, 3.0), (ContrastMetric::Apca, 45.0)] {
+            let theme = MinimumContrast::new(pastel.clone(), metric, minimum)
+                .expect("theme should be valid");
+            for input in [CONSISTENCY_STRING_1, CONSISTENCY_STRING_2, "a", "b", "c"] {
+                let hash = hash::hash_value(input);
+                let original = Oklch::from(pastel.main_color(&hash).expect("main color"));
+                let main = theme
+                    .main_color(&hash)
+                    .expect("could not adjust main color");
+                assert!(metric.contrast(main, background) >= minimum);
+                assert!(metric.contrast(main, background) < minimum + 1.0);
+                assert!(Oklch::from(main).lightness < original.lightness);
+                assert!((Oklch::from(main).hue - original.hue).abs() < 5.0);
+
+                assert_eq!(Some(main), theme.main_color(&hash).ok());
+                assert_eq!(Some(background), theme.background_color(&hash).ok(),);
+                let palette = theme.palette(&hash).expect("could not adjust palette");
+                assert_eq!(RGBA::from(main), palette[0]);
+                assert_eq!(3, palette.len());
+                for color in palette {
+                    assert!(metric.contrast(color.into(), background) >= minimum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn minimum_contrast_keeps_enough_contrast() {
+        let hash = hash::hash_value(CONSISTENCY_STRING_1);
+        let theme = MinimumContrast::new(default_theme(), ContrastMetric::Wcag2, 1.5)
+            .expect("theme should be valid");
+        assert_eq!(
+            default_theme().main_color(&hash).ok(),
+            theme.main_color(&hash).ok()
+        );
+
+        // Dark backgrounds lighten the color instead
+        let background: RGB = (20, 20, 20).into();
+        let dark = HSLRange::new(0.0, 360.0, 50.0, 50.0, 10.0, 10.0, vec![background])
+            .expect("theme should be valid");
+        let theme = MinimumContrast::new(Arc::new(dark), ContrastMetric::Wcag2, 4.5)
+            .expect("theme should be valid");
+        let main = theme
+            .main_color(&hash)
+            .expect("could not adjust main color");
+        assert!(Oklch::from(main).lightness > 0.5);
+    }
+
+    #[test]
+    fn minimum_contrast_validation() {
+        assert!(MinimumContrast::new(default_theme(), ContrastMetric::Wcag2, 22.0).is_err());
+        assert!(MinimumContrast::new(default_theme(), ContrastMetric::Apca, -1.0).is_err());
+        assert!(MinimumContrast::new(default_theme(), ContrastMetric::Apca, 60.0).is_ok());
+
+        // Neither black nor white reach a ratio of 7 on a middle gray
+        let gray = Selection::new(vec![(100, 100, 100).into()], vec![(119, 119, 119).into()])
+            .expect("theme should be valid");
+        let theme = MinimumContrast::new(Arc::new(gray), ContrastMetric::Wcag2, 7.0)
+            .expect("theme should be valid");
+        let error = theme
+            .main_color(&hash::hash_value(CONSISTENCY_STRING_1))
+            .err();
+        assert!(matches!(error, Some(ThemeError::ThemeContrastError(_))));
+    }
+
+    /// Returns the distance between two hues around the color wheel.
+    fn hue_distance(first: f32, second: f32) -> f32 {
+        let distance = (first - second).rem_euclid(360.0);
+        distance.min(360.0 - distance)
+    }
+
+    #[test]
+    fn hsl_range_hue_wraps_around() {
+        let theme = HSLRange::new(
+            330.0,
+            30.0,
+            100.0,
+            100.0,
+            50.0,
+            50.0,
+            vec![(0, 0, 0).into()],
+        )
+        .expect("theme should be valid");
+        let mut hues = Vec::new();
+        for input in 0..200 {
+            let hash = hash::hash_value(&input.to_string());
+            let hue = theme.banded_hue(((hash[0] as u16) << 8) | hash[1] as u16);
+            assert!(!(30.0..330.0).contains(&hue), "{hue}");
+            hues.push(hue);
+        }
+        assert!(hues.iter().any(|hue| *hue > 330.0));
+        assert!(hues.iter().any(|hue| *hue < 30.0));
+
+        assert_eq!(330.0, theme.banded_hue(u16::MIN));
+        assert_eq!(30.0, theme.banded_hue(u16::MAX));
+        assert!(hue_distance(0.0, theme.banded_hue(u16::MAX / 2)) < 0.01);
+    }
+
+    #[test]
+    fn hsl_range_hue_bands_are_uniform() {
+        let mut theme = HSLRange::new(200.0, 230.0, 100.0, 100.0, 50.0, 50.0, vec![])
+            .expect("theme should be valid");
+        theme.add_hue_band(20.0, 40.0).add_hue_band(350.0, 10.0);
+
+        // 30 + 20 + 20 degrees, mapped in order
+        assert_eq!(200.0, theme.banded_hue(u16::MIN));
+        assert!((theme.banded_hue(u16::MAX / 7 * 3) - 230.0).abs() < 0.01);
+        assert!((theme.banded_hue(u16::MAX / 7 * 4) - 30.0).abs() < 0.01);
+        assert!(hue_distance(0.0, theme.banded_hue(u16::MAX / 7 * 6)) < 0.01);
+        assert_eq!(10.0, theme.banded_hue(u16::MAX));
+
+        for input in 0..200 {
+            let hue = theme.banded_hue(input * 327);
+            assert!(
+                (200.0..=230.0).contains(&hue)
+                    || (20.0..=40.0).contains(&hue)
+                    || !(10.0..350.0).contains(&hue),
+                "{hue}"
+            );
+        }
+    }
+
+    #[test]
+    fn hsl_range_derived_backgrounds_work() {
+        let mut theme = HSLRange::new(0.0, 360.0, 60.0, 60.0, 50.0, 50.0, vec![])
+            .expect("theme should be valid");
+        let hash = hash::hash_value(CONSISTENCY_STRING_1);
+        assert!(theme.background_color(&hash).is_err());
+
+        let main = theme
+            .main_color(&hash)
+            .expect("could not generate main color");
+        let main_hue = Oklch::from(main).hue;
+        let background = |theme: &HSLRange| {
+            theme
+                .background_color(&hash)
+                .expect("could not generate background color")
+        };
+
+        theme.set_derived_background(Some(DerivedBackground::Complementary {
+            saturation: 60.0,
+            lightness: 50.0,
+        }));
+        let complementary = background(&theme);
+        assert_eq!(
+            complementary,
+            hsl_color((theme.hsl(&hash).0 + 180.0) % 360.0, 0.6, 0.5)
+        );
+        assert!(hue_distance(main_hue, Oklch::from(complementary).hue) > 90.0);
+
+        theme.set_derived_background(Some(DerivedBackground::Analogous {
+            hue_offset: -30.0,
+            saturation: 60.0,
+            lightness: 50.0,
+        }));
+        let analogous = background(&theme);
+        assert_ne!(main, analogous);
+        assert!(hue_distance(main_hue, Oklch::from(analogous).hue) < 60.0);
+
+        theme.set_derived_background(Some(DerivedBackground::Tint { lightness: 90.0 }));
+        let (hue, saturation, _) = theme.hsl(&hash);
+        let tint = background(&theme);
+        assert_eq!(hsl_color(hue, saturation, 0.9), tint);
+        assert!(Oklch::from(tint).lightness > Oklch::from(main).lightness);
+
+        theme.set_derived_background(Some(DerivedBackground::Range {
+            hue_min: 330.0,
+            hue_max: 30.0,
+            saturation_min: 20.0,
+            saturation_max: 30.0,
+            lightness_min: 85.0,
+            lightness_max: 95.0,
+        }));
+        let mut backgrounds = Vec::new();
+        for input in [
+            CONSISTENCY_STRING_1,
+            CONSISTENCY_STRING_2,
+            CONSISTENCY_STRING_3,
+        ] {
+            let color = theme
+                .background_color(&hash::hash_value(input))
+                .expect("could not generate background color");
+            assert!(Oklch::from(color).lightness > 0.85);
+            backgrounds.push(color);
+        }
+        assert_ne!(backgrounds[0], backgrounds[1]);
+        assert_ne!(backgrounds[1], backgrounds[2]);
+        assert_eq!(Some(main), theme.main_color(&hash).ok());
+
+        theme.set_derived_background(Some(DerivedBackground::Range {
+            hue_min: 0.0,
+            hue_max: 360.0,
+            saturation_min: 30.0,
+            saturation_max: 20.0,
+            lightness_min: 85.0,
+            lightness_max: 95.0,
+        }));
+        assert!(theme.background_color(&hash).is_err());
+        assert!(theme.main_color(&hash).is_err());
+
+        theme.set_derived_background(None);
+        assert!(theme.background_color(&hash).is_err());
+    }
+
     #[test]
     fn hsl_theme_validation() {
         let theme = HSLRange::new(
@@ -441,8 +1551,8 @@ mod tests {
         assert!(theme.is_ok());
 
         let theme = HSLRange::new(
-            360.0,
-            0.0,
+            400.0,
+            30.0,
             0.0,
             100.0,
             0.0,
@@ -455,6 +1565,11 @@ mod tests {
         );
         assert!(theme.is_err());
 
+        let mut theme = HSLRange::new(200.0, 230.0, 0.0, 100.0, 0.0, 100.0, vec![])
+            .expect("theme should be valid");
+        theme.add_hue_band(30.0, -20.0);
+        assert!(theme.main_color(&[0]).is_err());
+
         let theme = HSLRange::new(
             0.0,
             360.0,
@@ -537,6 +1652,95 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hsl_range_serde_works() {
+        let mut theme = HSLRange::new(10.0, 20.0, 30.0, 40.0, 50.0, 60.0, vec![(1, 2, 3).into()])
+            .expect("theme should be valid");
+        theme.set_palette_size(2).add_hue_band(330.0, 30.0);
+        let json = serde_json::to_string(&theme).expect("theme should serialize");
+        let deserialized: HSLRange = serde_json::from_str(&json).expect("theme should deserialize");
+        assert_eq!(2, deserialized.palette_size);
+        assert_eq!(vec![(330.0, 30.0)], deserialized.hue_bands);
+        assert_eq!(None, deserialized.derived_background);
+
+        test_theme_consistency(
+            CONSISTENCY_STRING_1,
+            Arc::new(deserialized),
+            theme
+                .main_color(&hash::hash_value(CONSISTENCY_STRING_1))
+                .expect("could not generate main color"),
+            (1, 2, 3).into(),
+        );
+
+        let derived = DerivedBackground::Tint { lightness: 90.0 };
+        theme.set_derived_background(Some(derived));
+        let json = serde_json::to_string(&theme).expect("theme should serialize");
+        let deserialized: HSLRange = serde_json::from_str(&json).expect("theme should deserialize");
+        assert_eq!(Some(derived), deserialized.derived_background);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn oklch_range_serde_works() {
+        let mut theme = OklchRange::new(10.0, 20.0, 0.1, 0.2, 50.0, 60.0, vec![(1, 2, 3).into()])
+            .expect("theme should be valid");
+        theme.set_palette_size(2);
+        let json = serde_json::to_string(&theme).expect("theme should serialize");
+        let deserialized: OklchRange =
+            serde_json::from_str(&json).expect("theme should deserialize");
+        assert_eq!(2, deserialized.palette_size);
+
+        let json = json.replace(r#""chroma_min":0.1"#, r#""chroma_min":0.3"#);
+        let error = serde_json::from_str::<OklchRange>(&json)
+            .err()
+            .expect("invalid theme should not deserialize");
+        assert!(
+            error
+                .to_string()
+                .starts_with("theme validation failed: chroma_max must be larger than chroma_min")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hsl_range_serde_validation() {
+        let json = r#"{
+            "hue_min": 400.0,
+            "hue_max": 30.0,
+            "saturation_min": 0.0,
+            "saturation_max": 100.0,
+            "lightness_min": 0.0,
+            "lightness_max": 100.0,
+            "background": [{"red": 0, "green": 0, "blue": 0}]
+        }"#;
+        let error = serde_json::from_str::<HSLRange>(json)
+            .err()
+            .expect("invalid theme should not deserialize");
+        assert!(
+            error
+                .to_string()
+                .starts_with("theme validation failed: hue ranges wrapping around 360")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn selection_serde_validation() {
+        let json = r#"{"main": [], "background": [{"red": 0, "green": 0, "blue": 0}]}"#;
+        let error = serde_json::from_str::<Selection>(json)
+            .err()
+            .expect("invalid theme should not deserialize");
+        assert!(
+            error
+                .to_string()
+                .starts_with("theme validation failed: main color selection is empty")
+        );
+
+        let json = r#"{"main": [{"red": 0, "green": 0, "blue": 0}], "background": [{"red": 0, "green": 0, "blue": 0}]}"#;
+        assert!(serde_json::from_str::<Selection>(json).is_ok());
+    }
+
     #[test]
     fn selection_theme_validation() {
         let theme = Selection::new(
@@ -0,0 +1,116 @@
+"""Renders the github-*.png golden images used by the tests in src/github.rs.
+
+This is a standalone implementation of the identicon algorithm the GitHub preset follows. It
+uses only the Python standard library and shares no code with identicon-rs, so the golden images
+check the crate against a second implementation of the algorithm. They are not GitHub's own
+output, which is derived from an account's numeric user ID rather than its login:
+
+- The input is hashed with MD5.
+- The hash is split into nibbles, high nibble first. The first 15 nibbles fill the columns of a
+  5x5 grid from the middle column outwards, top to bottom, and the left half is mirrored onto the
+  right half. A cell is filled when its nibble is even.
+- The color uses the last 28 bits of the hash: 12 bits for the hue, scaled to 0-360 degrees, then
+  a byte each lowering the saturation from 65% and the lightness from 75% by up to 20%.
+- The image is 420x420: 70 pixel cells with a 35 pixel border, on a (240, 240, 240) background.
+
+Run it from this directory with:
+
+    python3 github.py github identicon-rs conways-glider
+"""
+
+import hashlib
+import struct
+import sys
+import zlib
+
+SIZE = 5
+CELL_SIZE = 70
+BORDER = CELL_SIZE // 2
+IMAGE_SIZE = SIZE * CELL_SIZE + 2 * BORDER
+BACKGROUND = (240, 240, 240)
+
+
+def hue_to_rgb(a, b, hue):
+    if hue < 0:
+        hue += 1
+    elif hue > 1:
+        hue -= 1
+    if hue < 1 / 6:
+        return a + (b - a) * 6 * hue
+    if hue < 1 / 2:
+        return b
+    if hue < 2 / 3:
+        return a + (b - a) * (2 / 3 - hue) * 6
+    return a
+
+
+def hsl_to_rgb(hue, saturation, lightness):
+    hue /= 360
+    saturation /= 100
+    lightness /= 100
+    if lightness <= 0.5:
+        b = lightness * (saturation + 1)
+    else:
+        b = lightness + saturation - lightness * saturation
+    a = lightness * 2 - b
+    return tuple(
+        int(round(hue_to_rgb(a, b, channel) * 255))
+        for channel in (hue + 1 / 3, hue, hue - 1 / 3)
+    )
+
+
+def color(digest):
+    hue = ((digest[12] & 0x0F) << 8) | digest[13]
+    return hsl_to_rgb(
+        hue * 360 / 4095,
+        65 - digest[14] * 20 / 255,
+        75 - digest[15] * 20 / 255,
+    )
+
+
+def grid(digest):
+    nibbles = iter(nibble for byte in digest for nibble in (byte >> 4, byte & 0x0F))
+    cells = [False] * (SIZE * SIZE)
+    for column in (2, 1, 0):
+        for row in range(SIZE):
+            filled = next(nibbles) % 2 == 0
+            cells[row * SIZE + column] = filled
+            cells[row * SIZE + SIZE - 1 - column] = filled
+    return cells
+
+
+def chunk(kind, data):
+    crc = zlib.crc32(kind + data) & 0xFFFFFFFF
+    return struct.pack(">I", len(data)) + kind + data + struct.pack(">I", crc)
+
+
+def render(text):
+    digest = hashlib.md5(text.encode()).digest()
+    foreground = color(digest)
+    cells = grid(digest)
+
+    rows = []
+    for y in range(IMAGE_SIZE):
+        row = bytearray([0])
+        for x in range(IMAGE_SIZE):
+            cell_x, cell_y = x - BORDER, y - BORDER
+            pixel = BACKGROUND
+            inside = 0 <= cell_x < SIZE * CELL_SIZE and 0 <= cell_y < SIZE * CELL_SIZE
+            if inside and cells[cell_y // CELL_SIZE * SIZE + cell_x // CELL_SIZE]:
+                pixel = foreground
+            row += bytes(pixel)
+        rows.append(bytes(row))
+
+    header = struct.pack(">IIBBBBB", IMAGE_SIZE, IMAGE_SIZE, 8, 2, 0, 0, 0)
+    return (
+        b"\x89PNG\r\n\x1a\n"
+        + chunk(b"IHDR", header)
+        + chunk(b"IDAT", zlib.compress(b"".join(rows), 9))
+        + chunk(b"IEND", b"")
+    )
+
+
+if __name__ == "__main__":
+    for text in sys.argv[1:]:
+        with open(f"github-{text}.png", "wb") as file:
+            file.write(render(text))